@@ -0,0 +1,86 @@
+//! URL-safe base64 encoding of a UintArray, without padding.
+
+use crate::{UintArray, UintArrayError};
+use std::mem::size_of;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// 16 bytes are 128 bits, which is 22 characters of 6 bits each.
+const ENCODED_LEN: usize = 22;
+
+impl UintArray {
+    /// Encodes the little-endian bytes of the UintArray as URL-safe base64, without padding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>().extend(1..4);
+    ///
+    /// assert_eq!(ua.raw(), UintArray::from_base64(&ua.to_base64()).unwrap().raw());
+    /// ```
+    pub fn to_base64(&self) -> String {
+        let mut encoded = String::with_capacity(ENCODED_LEN);
+
+        // Every 3 bytes become 4 characters, the last byte becomes 2 characters.
+        for chunk in self.0.to_le_bytes().chunks(3) {
+            let mut group = [0; 3];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let bits = (group[0] as usize) << 16 | (group[1] as usize) << 8 | group[2] as usize;
+
+            for i in 0..=chunk.len() {
+                encoded.push(ALPHABET[bits >> (18 - i * 6) & 0b111111] as char);
+            }
+        }
+
+        encoded
+    }
+
+    /// Decodes a UintArray from the URL-safe base64 created by `to_base64`.
+    /// Returns an error if the string isn't valid or the decoded UintArray is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `encoded` - The base64 to decode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::from_base64("GgAIAAAAAAAAAAAAAAAAAA").unwrap();
+    ///
+    /// assert_eq!(Some(8), ua.at(2));
+    /// ```
+    pub fn from_base64(encoded: &str) -> Result<Self, UintArrayError> {
+        if encoded.len() != ENCODED_LEN {
+            return Err(UintArrayError::InvalidBase64);
+        }
+
+        let mut bytes = [0; size_of::<u128>()];
+
+        for (n, chunk) in encoded.as_bytes().chunks(4).enumerate() {
+            let mut bits = 0;
+
+            for (i, c) in chunk.iter().enumerate() {
+                let value = match ALPHABET.iter().position(|a| a == c) {
+                    Some(v) => v,
+                    None => return Err(UintArrayError::InvalidBase64),
+                };
+
+                bits |= value << (18 - i * 6);
+            }
+
+            let group = [(bits >> 16) as u8, (bits >> 8) as u8, bits as u8];
+            let len = chunk.len() - 1;
+
+            // Bits beyond the last byte must be zero for the encoding to be canonical.
+            if group[len..].iter().any(|b| *b != 0) {
+                return Err(UintArrayError::InvalidBase64);
+            }
+
+            bytes[n * 3..n * 3 + len].copy_from_slice(&group[..len]);
+        }
+
+        UintArray::_check_raw(u128::from_le_bytes(bytes))
+    }
+}
@@ -0,0 +1,71 @@
+//! In-place construction of a UintArray.
+
+use crate::{UintArray, META_BITS};
+
+/// Appends items to a UintArray in place, writing the length only when finished.
+///
+/// Created by `UintArray::with`.
+pub struct UintArrayBuilder {
+    data: u128,
+    size: u128,
+    len: u128,
+    // The largest length before the items reach into the tag, if there is one.
+    len_limit: u128,
+}
+
+impl UintArrayBuilder {
+    pub(crate) fn new(ua: UintArray) -> Self {
+        UintArrayBuilder {
+            // Clears a value stored with `set_spare` so it can't corrupt the appended items.
+            data: ua._grow(ua.len()),
+            size: ua.size(),
+            len: ua.len(),
+            len_limit: ua._len_limit(),
+        }
+    }
+
+    pub(crate) fn finish(self) -> UintArray {
+        UintArray(UintArray(self.data)._set_len(self.len))
+    }
+
+    /// Gets the current length of the array being built.
+    #[inline]
+    pub fn len(&self) -> u128 {
+        self.len
+    }
+
+    /// Whether the array being built contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends an item to the end.
+    /// Panics if appending would exceed capacity, reach into the tag or if the item doesn't fit
+    /// in the size.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to append.
+    pub fn append(&mut self, item: u128) -> &mut Self {
+        UintArray::_check_insert_panic("append", self.size, self.len, 1, item);
+        UintArray::_check_tag_panic("append", self.size, self.len, 1, self.len_limit);
+
+        self.data |= item << (self.len * self.size + META_BITS);
+        self.len += 1;
+        self
+    }
+
+    /// Appends every item of the iterator to the end.
+    /// Panics if appending would exceed capacity or if an item doesn't fit in the size.
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - Iterator of items to append.
+    pub fn extend<T: IntoIterator<Item = u128>>(&mut self, iter: T) -> &mut Self {
+        for i in iter {
+            self.append(i);
+        }
+        self
+    }
+}
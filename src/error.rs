@@ -0,0 +1,53 @@
+//! Errors returned by the fallible UintArray operations.
+
+use std::error::Error;
+use std::fmt;
+
+/// The reasons a UintArray operation can fail.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum UintArrayError {
+    /// The UintArray has no room for more items.
+    CapacityExceeded,
+    /// An item doesn't fit in the size of the UintArray.
+    ItemTooLarge,
+    /// The position is outside the UintArray.
+    OutOfBounds,
+    /// The size is larger than the largest element size.
+    InvalidSize,
+    /// The size is not a power of 2.
+    NotPowerOfTwo,
+    /// The length of the UintArray is greater than its capacity.
+    LengthExceedsCapacity,
+    /// The size of the UintArray doesn't match the size required by the operation.
+    SizeMismatch,
+    /// The length of the UintArray doesn't match the length required by the operation.
+    LengthMismatch,
+    /// The string is not valid base64 of a UintArray.
+    InvalidBase64,
+    /// The string is not a valid list of items.
+    InvalidFormat,
+    /// The UintArray doesn't fit in the requested type.
+    DoesNotFit,
+}
+
+impl fmt::Display for UintArrayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UintArrayError::CapacityExceeded => write!(f, "capacity exceeded"),
+            UintArrayError::ItemTooLarge => write!(f, "item does not fit in size"),
+            UintArrayError::OutOfBounds => write!(f, "position out of bounds"),
+            UintArrayError::InvalidSize => write!(f, "size exceeds the largest element size"),
+            UintArrayError::NotPowerOfTwo => write!(f, "size is not a power of 2"),
+            UintArrayError::LengthExceedsCapacity => write!(f, "length exceeds capacity"),
+            UintArrayError::SizeMismatch => write!(f, "size does not match the required size"),
+            UintArrayError::LengthMismatch => {
+                write!(f, "length does not match the required length")
+            }
+            UintArrayError::InvalidBase64 => write!(f, "invalid base64"),
+            UintArrayError::InvalidFormat => write!(f, "invalid list format"),
+            UintArrayError::DoesNotFit => write!(f, "does not fit in the requested type"),
+        }
+    }
+}
+
+impl Error for UintArrayError {}
@@ -0,0 +1,164 @@
+//! A UintArray with its size fixed by the type.
+
+use crate::{UintArray, UintArrayError, MAX_ELEMENT_SIZE};
+use std::convert::TryFrom;
+
+/// A UintArray whose values are `SIZE` bits, decided at compile time.
+///
+/// Since the size is part of the type, arrays of different sizes can't be mixed up.
+#[derive(Copy, Clone)]
+pub struct FixedUintArray<const SIZE: usize>(UintArray);
+
+impl<const SIZE: usize> FixedUintArray<SIZE> {
+    /// Fails to compile when `SIZE` isn't a power of 2 of at most `MAX_ELEMENT_SIZE`.
+    const VALID_SIZE: () = assert!(
+        SIZE.is_power_of_two() && SIZE <= MAX_ELEMENT_SIZE,
+        "SIZE must be a power of 2 of at most MAX_ELEMENT_SIZE"
+    );
+
+    /// How many elements can be stored in a FixedUintArray of this size.
+    /// Fails to compile if `SIZE` isn't a valid size for a UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::FixedUintArray;
+    ///
+    /// assert_eq!(15, FixedUintArray::<8>::CAPACITY);
+    /// assert_eq!(31, FixedUintArray::<1>::CAPACITY);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use uintarray::FixedUintArray;
+    ///
+    /// let cap = FixedUintArray::<3>::CAPACITY;
+    /// ```
+    pub const CAPACITY: u128 = {
+        let () = Self::VALID_SIZE;
+        UintArray::_max_len(SIZE as u128)
+    };
+
+    /// Creates a new empty FixedUintArray.
+    /// Fails to compile if `SIZE` isn't a valid size for a UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::FixedUintArray;
+    /// let ua = FixedUintArray::<4>::new();
+    ///
+    /// assert_eq!(0, ua.len());
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use uintarray::FixedUintArray;
+    /// let ua = FixedUintArray::<128>::new();
+    /// ```
+    pub fn new() -> Self {
+        let () = Self::VALID_SIZE;
+        FixedUintArray(UintArray::new_size(SIZE))
+    }
+
+    /// Gets the current length of the FixedUintArray.
+    #[inline]
+    pub fn len(&self) -> u128 {
+        self.0.len()
+    }
+
+    /// Whether the FixedUintArray contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the item at position `pos`. Returns None if out of bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the item to get.
+    #[inline]
+    pub fn at(&self, pos: u128) -> Option<u128> {
+        self.0.at(pos)
+    }
+
+    /// Creates a new FixedUintArray with the given item appended to the end.
+    /// Panics if appending would exceed capacity or if the item doesn't fit in `SIZE` bits.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to append.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::FixedUintArray;
+    /// let ua = FixedUintArray::<4>::new();
+    ///
+    /// let ua = ua
+    ///     .append(1)
+    ///     .append(2);
+    ///
+    /// assert_eq!(Some(2), ua.at(1));
+    /// ```
+    pub fn append(&self, item: u128) -> Self {
+        FixedUintArray(self.0.append(item))
+    }
+
+    /// Creates a new FixedUintArray with the given item inserted at the given position.
+    /// Panics if inserting would exceed capacity or if the item doesn't fit in `SIZE` bits.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - The position to insert the item at.
+    /// * `item` - The item to insert.
+    pub fn insert(&self, pos: u128, item: u128) -> Self {
+        FixedUintArray(self.0.insert(pos, item))
+    }
+
+    /// Returns the underlying UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::FixedUintArray;
+    /// let ua = FixedUintArray::<8>::new().append(3);
+    ///
+    /// assert_eq!(8, ua.array().size());
+    /// ```
+    #[inline]
+    pub fn array(&self) -> UintArray {
+        self.0
+    }
+}
+
+impl<const SIZE: usize> Default for FixedUintArray<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SIZE: usize> From<FixedUintArray<SIZE>> for UintArray {
+    fn from(ua: FixedUintArray<SIZE>) -> Self {
+        ua.0
+    }
+}
+
+impl<const SIZE: usize> TryFrom<UintArray> for FixedUintArray<SIZE> {
+    type Error = UintArrayError;
+
+    /// Wraps a UintArray, returning an error if its size isn't `SIZE`.
+    fn try_from(ua: UintArray) -> Result<Self, Self::Error> {
+        let () = Self::VALID_SIZE;
+        ua._check_size(SIZE as u128)?;
+        Ok(FixedUintArray(ua))
+    }
+}
+
+impl<const SIZE: usize> IntoIterator for FixedUintArray<SIZE> {
+    type Item = u128;
+    type IntoIter = crate::UintArrayIterator;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
@@ -1,8 +1,23 @@
 //! An array packed in a single uint.
 
+use std::collections::{BTreeMap, HashMap};
 use std::convert::From;
+use std::fmt;
 use std::iter::IntoIterator;
 use std::mem::size_of;
+use std::str::FromStr;
+
+#[cfg(feature = "base64")]
+mod base64;
+mod builder;
+mod error;
+mod fixed;
+mod reverse;
+
+pub use builder::UintArrayBuilder;
+pub use error::UintArrayError;
+pub use fixed::FixedUintArray;
+pub use reverse::{ReverseUintArray, ReverseUintArrayIterator};
 
 // Mask for the size part in the UintArray.
 const SIZE_MASK: u128 = 0b111;
@@ -12,15 +27,24 @@ const SIZE_BITS: u128 = 3;
 const LEN_MASK: u128 = 0b11111 << SIZE_BITS;
 const LEN_BITS: u128 = 5;
 
+// The largest length the length part can hold.
+const MAX_LEN: u128 = LEN_MASK >> SIZE_BITS;
+
 // Meta makes up the non-data part of the UintArray.
 // const META_MASK: u128 = SIZE_MASK | LEN_MASK;
 const META_BITS: u128 = SIZE_BITS + LEN_BITS;
 
+/// The largest element size in bits, half the size of the uint backing a UintArray.
+pub const MAX_ELEMENT_SIZE: usize = size_of::<u128>() * 4;
+
+/// The number of high bits of the uint used for the tag set by `UintArray::with_tag`.
+pub const TAG_BITS: usize = 8;
+
 /// Multiple values stored in a single uint.
 ///
 /// Can only contain values of the type specified at creation time.
 #[derive(Copy, Clone)]
-pub struct UintArray(pub u128);
+pub struct UintArray(u128);
 
 /// Iteration over a UintArray.
 pub struct UintArrayIterator {
@@ -33,10 +57,7 @@ impl IntoIterator for UintArray {
     type IntoIter = UintArrayIterator;
 
     fn into_iter(self) -> Self::IntoIter {
-        UintArrayIterator {
-            ua: self,
-            index: 0,
-        }
+        UintArrayIterator { ua: self, index: 0 }
     }
 }
 
@@ -44,8 +65,27 @@ impl Iterator for UintArrayIterator {
     type Item = u128;
 
     fn next(&mut self) -> Option<u128> {
+        // Only advance within bounds, so a start past the end can't overflow.
+        let item = self.ua.at(self.index)?;
         self.index += 1;
-        self.ua.at(self.index - 1)
+        Some(item)
+    }
+
+    // Reading the size and length once and stepping the offset avoids decoding the header
+    // and bounds checking for every item, which `sum()` and `collect()` would do through `next`.
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, u128) -> B,
+    {
+        let size = self.ua.size();
+        let mask = UintArray::_mask(size);
+        let mut acc = init;
+
+        for i in self.index..self.ua.len() {
+            acc = f(acc, self.ua.0 >> (i * size + META_BITS) & mask);
+        }
+
+        acc
     }
 }
 
@@ -75,6 +115,132 @@ impl From<u128> for UintArray {
     }
 }
 
+impl From<UintArray> for u128 {
+    /// Returns the uint backing the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let raw: u128 = UintArray::from(69420).into();
+    ///
+    /// assert_eq!(69420, raw);
+    /// ```
+    fn from(ua: UintArray) -> Self {
+        ua.0
+    }
+}
+
+impl AsRef<u128> for UintArray {
+    fn as_ref(&self) -> &u128 {
+        &self.0
+    }
+}
+
+impl Extend<u128> for UintArray {
+    /// Extends the UintArray in place with the values of the iterator.
+    /// Panics if inserting would exceed the capacity or an item is greater than size.
+    ///
+    /// Method call syntax resolves to the inherent `UintArray::extend`, which returns a new
+    /// UintArray and leaves `self` as is, so this must be called through the trait. Discarding
+    /// the result of the inherent `extend` warns, which catches calling it by mistake.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let mut ua = UintArray::new::<u8>();
+    ///
+    /// Extend::extend(&mut ua, 1..4);
+    ///
+    /// assert_eq!(3, ua.len());
+    /// ```
+    fn extend<T: IntoIterator<Item = u128>>(&mut self, iter: T) {
+        *self = UintArray::extend(self, iter);
+    }
+}
+
+impl fmt::Display for UintArray {
+    /// Formats the items of the UintArray as a list, e.g. `[1, 2, 3]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>().extend(1..4);
+    ///
+    /// assert_eq!("[1, 2, 3]", ua.to_string());
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+
+        for (i, item) in self.into_iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", item)?;
+        }
+
+        write!(f, "]")
+    }
+}
+
+impl FromStr for UintArray {
+    type Err = UintArrayError;
+
+    /// Parses a list of items, e.g. `[1, 2, 3]`, into a UintArray with the smallest size
+    /// that fits all of the items.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The list to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua: UintArray = "[1, 2, 3]".parse().unwrap();
+    ///
+    /// assert_eq!(2, ua.size());
+    /// assert_eq!(Some(2), ua.at(1));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if !s.starts_with('[') || !s.ends_with(']') || s.len() < 2 {
+            return Err(UintArrayError::InvalidFormat);
+        }
+
+        let inner = s[1..s.len() - 1].trim();
+        let mut items = Vec::new();
+
+        if !inner.is_empty() {
+            for item in inner.split(',') {
+                let item = item.trim();
+
+                // `parse` accepts a leading '+', which `from_json_number` rejects too.
+                if item.is_empty() || !item.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(UintArrayError::InvalidFormat);
+                }
+
+                match item.parse::<u128>() {
+                    Ok(i) => items.push(i),
+                    Err(_) => return Err(UintArrayError::InvalidFormat),
+                }
+            }
+        }
+
+        let max = items.iter().copied().max().unwrap_or(0);
+        let size = Self::_size_for_max(max).ok_or(UintArrayError::ItemTooLarge)?;
+
+        if items.len() as u128 > Self::_max_len(size as u128) {
+            return Err(UintArrayError::CapacityExceeded);
+        }
+
+        Ok(Self::new_size(size).extend(items))
+    }
+}
+
 impl UintArray {
     /// Creates a new UintArray with a specific data type.
     /// Size of the data type cannot be more than half of the UintArray data type size.
@@ -108,7 +274,7 @@ impl UintArray {
     /// assert_eq!(16, ua.size());
     /// ```
     pub fn new_size(size: usize) -> Self {
-        if size > size_of::<u128>() * 4 {
+        if size > MAX_ELEMENT_SIZE {
             panic!("Size must not be more than half of the UintArray data type size.");
         }
 
@@ -122,325 +288,3156 @@ impl UintArray {
         UintArray(size_log_u)
     }
 
-    /// Creates a bit mask for a value of `size` bits.
-    #[inline]
-    fn _mask(size: u128) -> u128 {
-        (1 << size) - 1
-    }
-
-    /// Updates the length of the UintArray.
-    #[inline]
-    fn _set_len(&self, new_len: u128) -> u128 {
-        (self.0 & !LEN_MASK) | new_len << SIZE_BITS
-    }
-
-    /// Panics if a value cannot be inserted.
-    fn _check_insert_panic(size: u128, len: u128, item: u128) {
-        if len >= Self::_cap(size) {
-            panic!("Attempted inserting beyond capacity.");
-        }
-
-        if Self::_mask(size) & item != item {
-            panic!("item={} does not fit in size={}", item, size);
-        }
-    }
-
-    /// Gets the bit size of values stored in the UintArray.
-    /// Same as what is passed to new_size().
+    /// Creates a new UintArray from the given uint without checking that it's valid.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Source UintArray.
+    ///
+    /// # Safety
+    ///
+    /// The length encoded in `data` must not exceed the capacity for the encoded size.
+    /// Other methods assume this and may panic or return garbage otherwise.
     ///
     /// # Examples
     ///
     /// ```
     /// use uintarray::UintArray;
-    /// let ua = UintArray::new_size(2);
+    /// let ua = unsafe { UintArray::from_raw(69420) };
     ///
-    /// assert_eq!(2, ua.size());
+    /// assert_eq!(16, ua.size());
     /// ```
     #[inline]
-    pub fn size(&self) -> u128 {
-        Self::_size(self.0)
+    pub unsafe fn from_raw(data: u128) -> Self {
+        UintArray(data)
     }
 
-    /// Gets the size encoded in `data`.
+    /// Gets the uint backing the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::from(69420);
+    ///
+    /// assert_eq!(69420, ua.raw());
+    /// ```
     #[inline]
-    fn _size(data: u128) -> u128 {
-        1 << (data & SIZE_MASK)
+    pub fn raw(&self) -> u128 {
+        self.0
     }
 
-    /// Gets the current length of the UintArray.
+    /// Creates a valid UintArray from a possibly invalid one by reducing the length to at most
+    /// the capacity and clearing all bits after the last item.
     ///
     /// # Examples
     ///
     /// ```
     /// use uintarray::UintArray;
-    /// let ua = UintArray::new_size(2);
     ///
-    /// let ua = ua
-    ///     .append(1)
-    ///     .append(2);
+    /// // Length 31 exceeds the capacity of 7 for size 16.
+    /// let ua = unsafe { UintArray::from_raw(u128::MAX << 8 | 31 << 3 | 4) }.repair();
     ///
-    /// assert_eq!(2, ua.len());
+    /// assert_eq!(7, ua.len());
+    /// assert_eq!(128, ua.bits_used() + ua.spare_bits());
     /// ```
-    #[inline]
-    pub fn len(&self) -> u128 {
-        Self::_len(self.0)
+    pub fn repair(&self) -> Self {
+        let len = self.len().min(self.cap());
+        UintArray(self._set_len(len)).normalize()
     }
 
-    /// Gets the length encoded in `data`.
-    #[inline]
-    fn _len(data: u128) -> u128 {
-        (data & LEN_MASK) >> SIZE_BITS
+    /// Creates a new UintArray with all bits after the last item cleared, so that UintArrays
+    /// with the same size and items are backed by the same uint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>().extend(1..4);
+    ///
+    /// assert_eq!(ua.raw(), ua.set_spare(42).normalize().raw());
+    /// ```
+    pub fn normalize(&self) -> Self {
+        let used = self.bits_used();
+
+        if used >= size_of::<u128>() as u128 * 8 {
+            return *self;
+        }
+
+        UintArray(self.0 & Self::_mask(used))
     }
 
-    /// How many elements can be stored in the UintArray - its capacity.
+    /// Whether the UintArray has a valid size, a length within its capacity and no bits set
+    /// after the last item, other than a tag stored with `with_tag`.
+    /// A value stored with `set_spare` below the tag therefore makes the UintArray invalid until
+    /// it's normalized.
     ///
     /// # Examples
     ///
     /// ```
     /// use uintarray::UintArray;
-    /// let ua = UintArray::new_size(4);
+    /// let ua = UintArray::new::<u8>().extend(1..4);
     ///
-    /// assert_eq!(30, ua.cap());
+    /// assert!(ua.is_valid());
+    /// assert!(!ua.set_spare(1).is_valid());
     /// ```
-    #[inline]
-    pub fn cap(&self) -> u128 {
-        Self::_cap(self.size())
+    pub fn is_valid(&self) -> bool {
+        Self::_check_size_valid(self.size() as usize).is_ok()
+            && self.len() <= self.cap()
+            && self.0 & !self._tag_bits() == self.normalize().0
     }
 
-    /// Returns the capacity of a UintArray with size `size`.
-    #[inline]
-    fn _cap(size: u128) -> u128 {
-        (size_of::<u128>() as u128 * 8 - META_BITS) / size
+    /// Decomposes the UintArray into its size, length and items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>().extend(1..4);
+    ///
+    /// assert_eq!((8, 3, vec![1, 2, 3]), ua.into_parts());
+    /// ```
+    pub fn into_parts(&self) -> (u128, u128, Vec<u128>) {
+        (self.size(), self.len(), self.into_iter().collect())
     }
 
-    /// Get the item at position `pos`. First item is at `pos = 0` (i.e. it's zero-indexed).
-    /// Returns None if out of bounds.
+    /// Creates a new UintArray with the given size and items, the inverse of `into_parts`.
+    /// Returns an error if the size is invalid, the items exceed capacity or an item doesn't
+    /// fit in the size.
     ///
     /// # Arguments
     ///
-    /// * `pos` - Position of the item to get.
+    /// * `size` - The size in bits of the contained data.
+    /// * `items` - The items of the UintArray.
     ///
     /// # Examples
     ///
     /// ```
     /// use uintarray::UintArray;
-    /// let ua = UintArray::new_size(4);
-    ///
-    /// let ua = ua
-    ///     .append(2)
-    ///     .append(4)
-    ///     .append(8);
+    /// let ua = UintArray::from_parts(8, &[1, 2, 3]).unwrap();
     ///
-    /// assert_eq!(Some(4), ua.at(1))
+    /// assert_eq!((8, 3, vec![1, 2, 3]), ua.into_parts());
     /// ```
-    pub fn at(&self, pos: u128) -> Option<u128> {
-        if pos >= self.len() {
-            None
-        } else {
-            let size = self.size();
-            let offset = size * pos + META_BITS;
-            self._at(size, offset)
+    pub fn from_parts(size: usize, items: &[u128]) -> Result<Self, UintArrayError> {
+        Self::_check_size_valid(size)?;
+
+        let len = items.len() as u128;
+        let ua = Self::new_size(size);
+        let size = size as u128;
+        let mut data = ua._set_len(len);
+
+        for (i, item) in (0..).zip(items) {
+            Self::_check_insert(size, i, 1, *item)?;
+            data |= item << (i * size + META_BITS);
         }
-    }
 
-    /// Get the item at a given position, disregarding whether it exists.
-    #[inline]
-    fn _at(&self, size: u128, offset: u128) -> Option<u128> {
-        Some((Self::_mask(size) << offset & self.0) >> offset)
+        Ok(UintArray(data))
     }
 
-    /// Creates a new UintArray with the given item appended to the end.
-    /// Panics if appending would exceed capacity or if the item doesn't fit in the UintArray size.
+    /// Creates a new UintArray with the smallest size that fits all of the items.
+    /// Panics if an item doesn't fit in the largest element size or the items exceed capacity.
     ///
     /// # Arguments
     ///
-    /// * `item` - Item to append.
+    /// * `items` - The items of the UintArray.
     ///
     /// # Examples
     ///
     /// ```
     /// use uintarray::UintArray;
-    /// let ua = UintArray::new_size(4);
-    ///
-    /// let ua = ua
-    ///     .append(1)
-    ///     .append(2)
-    ///     .append(3);
+    /// let ua = UintArray::auto(&[1, 2, 255]);
     ///
-    /// assert_eq!(Some(1), ua.at(0));
-    /// assert_eq!(3, ua.len());
+    /// assert_eq!(8, ua.size());
+    /// assert_eq!(vec![1, 2, 255], ua.into_iter().collect::<Vec<u128>>());
     /// ```
-    pub fn append(&self, item: u128) -> Self {
-        let len = self.len();
-        let size = self.size();
+    pub fn auto(items: &[u128]) -> Self {
+        let max = items.iter().copied().max().unwrap_or(0);
+        let size = Self::size_for_max(max);
 
-        Self::_check_insert_panic(size, len, item);
+        Self::_check_capacity_panic("auto", size as u128, 0, items.len() as u128);
 
-        self._append(item, size, len)
+        Self::new_size(size).extend(items.iter().copied())
     }
 
-    #[inline]
-    fn _append(&self, item:u128, size: u128, len: u128) -> Self {
-        UintArray(self._set_len(len + 1) | item << len * size + META_BITS)
+    /// Packs the items into as many UintArrays with the given size as needed, filling each one
+    /// before starting the next.
+    /// Panics if the size is invalid or an item doesn't fit in the size.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size in bits of the contained data.
+    /// * `items` - The items to pack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let arrays = UintArray::chunk_into_arrays(64, &[1, 2, 3]);
+    ///
+    /// assert_eq!(3, arrays.len());
+    /// assert_eq!(Some(3), arrays[2].at(0));
+    /// ```
+    pub fn chunk_into_arrays(size: usize, items: &[u128]) -> Vec<UintArray> {
+        let ua = Self::new_size(size);
+        let chunk_len = Self::_max_len(size as u128) as usize;
+
+        items
+            .chunks(chunk_len)
+            .map(|chunk| ua.extend(chunk.iter().copied()))
+            .collect()
     }
 
-    /// Creates a new UintArray with the given item inserted at the given position.
-    /// Panics if appending would exceed capacity or if the item doesn't fit in the UintArray size.
+    /// Creates a new UintArray with the given size from run-length encoded items, the inverse
+    /// of `rle`. Returns an error if the size is invalid, the items exceed capacity or an item
+    /// doesn't fit in the size.
     ///
     /// # Arguments
     ///
-    /// * `pos` - The position to insert the item at.
-    /// * `item` - The item to insert.
+    /// * `size` - The size in bits of the contained data.
+    /// * `runs` - Pairs of an item and how many times it's repeated.
     ///
     /// # Examples
     ///
     /// ```
     /// use uintarray::UintArray;
-    /// let ua = UintArray::new_size(4);
-    ///
-    /// let ua = ua
-    ///     .append(1)
-    ///     .append(2)
-    ///     .insert(1, 3);
+    /// let ua = UintArray::from_rle(8, &[(1, 2), (2, 1)]).unwrap();
     ///
-    /// assert_eq!(Some(3), ua.at(1));
+    /// assert_eq!(vec![1, 1, 2], ua.into_iter().collect::<Vec<u128>>());
     /// ```
-    pub fn insert(&self, pos: u128, item: u128) -> Self {
-        let len = self.len();
-        let size = self.size();
-        Self::_check_insert_panic(size, len, item);
+    pub fn from_rle(size: usize, runs: &[(u128, u128)]) -> Result<Self, UintArrayError> {
+        Self::_check_size_valid(size)?;
 
-        if pos > len {
-            return self._append(item, size, len);
+        // Check the total length before expanding, since the counts can be huge.
+        let mut len: u128 = 0;
+        for (_, count) in runs {
+            len = len.saturating_add(*count);
         }
 
-        let offset = pos * size + META_BITS;
-        let pos_mask = Self::_mask(offset);
+        if len > Self::_max_len(size as u128) {
+            return Err(UintArrayError::CapacityExceeded);
+        }
 
-        // Pushes everything after the offset off by `size` and inserts the item inbetween.
-        //
-        // If offset is at 4, the `size` is 2 and the new item is AA,
-        // it will do the following:
-        //
-        // 000011110000 -> 0000    0000 -> 001111  0000 -> 001111AA0000
-        //                   1111                AA
-        UintArray(self._set_len(len + 1) & pos_mask | (self.0 & !pos_mask) << size | item << offset)
+        let mut items = Vec::new();
+        for (item, count) in runs {
+            items.extend((0..*count).map(|_| *item));
+        }
+
+        Self::from_parts(size, &items)
     }
 
-    /// Extends the UintArray with the values of the iterator.
-    /// Panics if inserting would exceed the capacity or an item is greater than size.
+    /// Returns an error if `size` is not a valid size.
+    fn _check_size_valid(size: usize) -> Result<(), UintArrayError> {
+        if size > MAX_ELEMENT_SIZE {
+            return Err(UintArrayError::InvalidSize);
+        }
+
+        if !size.is_power_of_two() {
+            return Err(UintArrayError::NotPowerOfTwo);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the smallest valid size that fits `max`.
+    /// Panics if `max` doesn't fit in the largest element size.
     ///
     /// # Arguments
     ///
-    /// * `iter` - Iterator of items to append.
+    /// * `max` - The largest item the size must fit.
     ///
     /// # Examples
     ///
     /// ```
     /// use uintarray::UintArray;
-    /// let ua = UintArray::new::<u8>();
     ///
-    /// let ua = ua.extend(1..4);
-    ///
-    /// assert_eq!(Some(2), ua.at(1));
-    /// assert_eq!(3, ua.len());
+    /// assert_eq!(4, UintArray::size_for_max(5));
+    /// assert_eq!(8, UintArray::size_for_max(16));
     /// ```
+    pub fn size_for_max(max: u128) -> usize {
+        match Self::_size_for_max(max) {
+            Some(size) => size,
+            None => Self::_panic(
+                "size_for_max",
+                format_args!("max={} does not fit", max),
+                MAX_ELEMENT_SIZE as u128,
+                0,
+            ),
+        }
+    }
+
+    /// Returns the smallest valid size that fits `max`, or None if no size fits it.
+    fn _size_for_max(max: u128) -> Option<usize> {
+        let mut size = 1;
+
+        while size < MAX_ELEMENT_SIZE && Self::_mask(size as u128) & max != max {
+            size *= 2;
+        }
+
+        if Self::_mask(size as u128) & max != max {
+            return None;
+        }
+
+        Some(size)
+    }
+
+    /// Creates a bit mask for a value of `size` bits.
+    #[inline]
+    fn _mask(size: u128) -> u128 {
+        (1 << size) - 1
+    }
+
+    /// Updates the length of the UintArray.
+    #[inline]
+    fn _set_len(&self, new_len: u128) -> u128 {
+        (self.0 & !LEN_MASK) | new_len << SIZE_BITS
+    }
+
+    /// Panics in `method` with `reason`, followed by the length, capacity and size.
+    fn _panic(method: &str, reason: fmt::Arguments, size: u128, len: u128) -> ! {
+        panic!(
+            "{}: {} (len={}, cap={}, size={})",
+            method,
+            reason,
+            len,
+            Self::_max_len(size),
+            size
+        );
+    }
+
+    /// Panics if `added` items cannot be inserted by `method`, or if `item` doesn't fit.
+    fn _check_insert_panic(method: &str, size: u128, len: u128, added: u128, item: u128) {
+        Self::_check_capacity_panic(method, size, len, added);
+        Self::_check_fits_panic(method, "item", item, size, len);
+    }
+
+    /// Panics if `value`, called `name` in the message, doesn't fit in the size for `method`.
+    fn _check_fits_panic(method: &str, name: &str, value: u128, size: u128, len: u128) {
+        if Self::_mask(size) & value != value {
+            Self::_panic(
+                method,
+                format_args!("{}={} does not fit", name, value),
+                size,
+                len,
+            );
+        }
+    }
+
+    /// Panics if `other`, called `name` in the message, doesn't have the same size for
+    /// `method`.
+    fn _check_same_size_panic(&self, method: &str, name: &str, other: &UintArray) {
+        if !self.same_shape(other) {
+            Self::_panic(
+                method,
+                format_args!("{} size={} does not match", name, other.size()),
+                self.size(),
+                self.len(),
+            );
+        }
+    }
+
+    /// Panics if `added` items cannot be inserted by `method` without exceeding capacity.
+    fn _check_capacity_panic(method: &str, size: u128, len: u128, added: u128) {
+        if len.saturating_add(added) > Self::_max_len(size) {
+            Self::_panic(method, format_args!("capacity exceeded"), size, len);
+        }
+    }
+
+    /// Panics if `added` items appended by `method` would reach into a tag that leaves room for
+    /// at most `limit` items, as returned by `_len_limit`.
+    fn _check_tag_panic(method: &str, size: u128, len: u128, added: u128, limit: u128) {
+        if len.saturating_add(added) > limit {
+            Self::_panic(
+                method,
+                format_args!("items would reach into the tag"),
+                size,
+                len,
+            );
+        }
+    }
+
+    /// Returns an error if `added` items cannot be inserted, or if `item` doesn't fit.
+    fn _check_insert(size: u128, len: u128, added: u128, item: u128) -> Result<(), UintArrayError> {
+        if len.saturating_add(added) > Self::_max_len(size) {
+            return Err(UintArrayError::CapacityExceeded);
+        }
+
+        if Self::_mask(size) & item != item {
+            return Err(UintArrayError::ItemTooLarge);
+        }
+
+        Ok(())
+    }
+
+    /// Gets the bit size of values stored in the UintArray.
+    /// Same as what is passed to new_size().
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(2);
+    ///
+    /// assert_eq!(2, ua.size());
+    /// ```
+    #[inline]
+    pub fn size(&self) -> u128 {
+        Self::_size(self.0)
+    }
+
+    /// Gets the size encoded in `data`.
+    #[inline]
+    fn _size(data: u128) -> u128 {
+        1 << (data & SIZE_MASK)
+    }
+
+    /// Gets the current length of the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(2);
+    ///
+    /// let ua = ua
+    ///     .append(1)
+    ///     .append(2);
+    ///
+    /// assert_eq!(2, ua.len());
+    /// ```
+    #[inline]
+    pub fn len(&self) -> u128 {
+        Self::_len(self.0)
+    }
+
+    /// Whether the UintArray contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(2);
+    ///
+    /// assert!(ua.is_empty());
+    /// assert!(!ua.append(1).is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the length encoded in `data`.
+    #[inline]
+    fn _len(data: u128) -> u128 {
+        (data & LEN_MASK) >> SIZE_BITS
+    }
+
+    /// How many elements can be stored in the UintArray - its capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// assert_eq!(30, ua.cap());
+    /// ```
+    #[inline]
+    pub fn cap(&self) -> u128 {
+        Self::_cap(self.size())
+    }
+
+    /// The largest item that fits in the UintArray size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// assert_eq!(15, ua.max_value());
+    /// ```
+    #[inline]
+    pub fn max_value(&self) -> u128 {
+        Self::_mask(self.size())
+    }
+
+    /// How many bits of the uint can never hold items because they don't fit another item.
+    /// Sizes 1 and 2 waste most of the uint, since the length can't exceed 31.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(64);
+    ///
+    /// assert_eq!(56, ua.wasted_bits());
+    /// assert_eq!(89, UintArray::new_size(1).wasted_bits());
+    /// ```
+    #[inline]
+    pub fn wasted_bits(&self) -> u128 {
+        let size = self.size();
+        Self::backing_bits() - META_BITS - Self::_max_len(size) * size
+    }
+
+    /// The capacity of a UintArray created with `new::<T>()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// const CAP: u128 = UintArray::capacity_of::<u8>();
+    ///
+    /// assert_eq!(15, CAP);
+    /// ```
+    #[inline]
+    pub const fn capacity_of<T>() -> u128 {
+        Self::_cap(size_of::<T>() as u128 * 8)
+    }
+
+    /// The number of bits in the uint backing a UintArray, including its length and size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    ///
+    /// assert_eq!(128, UintArray::backing_bits());
+    /// ```
+    #[inline]
+    pub const fn backing_bits() -> u128 {
+        size_of::<u128>() as u128 * 8
+    }
+
+    /// Returns the capacity of a UintArray with size `size`.
+    #[inline]
+    pub(crate) const fn _cap(size: u128) -> u128 {
+        (Self::backing_bits() - META_BITS) / size
+    }
+
+    /// Returns how many items a UintArray with size `size` can hold, which is less than the
+    /// capacity for sizes 1 and 2 since the length can't exceed 31.
+    #[inline]
+    pub(crate) const fn _max_len(size: u128) -> u128 {
+        let cap = Self::_cap(size);
+
+        if cap < MAX_LEN {
+            cap
+        } else {
+            MAX_LEN
+        }
+    }
+
+    /// How many bits of the uint are used by the UintArray, including its length and size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua.extend(1..4);
+    ///
+    /// assert_eq!(20, ua.bits_used());
+    /// ```
+    #[inline]
+    pub fn bits_used(&self) -> u128 {
+        META_BITS + self.len() * self.size()
+    }
+
+    /// How many of the high bits of the uint are not used by the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua.extend(1..4);
+    ///
+    /// assert_eq!(108, ua.spare_bits());
+    /// ```
+    #[inline]
+    pub fn spare_bits(&self) -> u128 {
+        size_of::<u128>() as u128 * 8 - self.bits_used()
+    }
+
+    /// Gets the value stored in the spare bits with `set_spare`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .append(1)
+    ///     .set_spare(42);
+    ///
+    /// assert_eq!(42, ua.spare());
+    /// ```
+    pub fn spare(&self) -> u128 {
+        let offset = self.bits_used();
+
+        if offset == size_of::<u128>() as u128 * 8 {
+            return 0;
+        }
+
+        self.0 >> offset
+    }
+
+    /// Creates a new UintArray with `value` stored in the spare bits, leaving the items as is.
+    /// Panics if the value doesn't fit in the spare bits.
+    ///
+    /// Appending items clears the value so it can't corrupt them, apart from its highest
+    /// `TAG_BITS` bits, which are kept as the tag (see `with_tag`). Other operations that change
+    /// the length of the UintArray assume the spare bits are zero, so the value should be set
+    /// last.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .extend(1..4)
+    ///     .set_spare(42);
+    ///
+    /// assert_eq!(3, ua.len());
+    /// assert_eq!(Some(3), ua.at(2));
+    /// ```
+    pub fn set_spare(&self, value: u128) -> Self {
+        let spare_bits = self.spare_bits();
+
+        if value >> spare_bits != 0 {
+            Self::_panic(
+                "set_spare",
+                format_args!("value={} does not fit in spare_bits={}", value, spare_bits),
+                self.size(),
+                self.len(),
+            );
+        }
+
+        if spare_bits == 0 {
+            return *self;
+        }
+
+        let offset = size_of::<u128>() as u128 * 8 - spare_bits;
+        UintArray(self.0 & Self::_mask(offset) | value << offset)
+    }
+
+    /// Gets the tag stored in the highest `TAG_BITS` bits with `with_tag`.
+    /// Returns 0 if the items reach into the tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .append(1)
+    ///     .with_tag(42);
+    ///
+    /// assert_eq!(42, ua.tag());
+    /// ```
+    pub fn tag(&self) -> u128 {
+        self._tag_bits() >> (Self::backing_bits() - TAG_BITS as u128)
+    }
+
+    /// Returns the tag bits in place, or 0 if the items reach into the tag.
+    fn _tag_bits(&self) -> u128 {
+        if self.spare_bits() < TAG_BITS as u128 {
+            return 0;
+        }
+
+        self.0 & !Self::_mask(Self::backing_bits() - TAG_BITS as u128)
+    }
+
+    /// Returns the largest length the UintArray can grow to, which is lower while it has a tag
+    /// since the items can't reach into it.
+    fn _len_limit(&self) -> u128 {
+        let size = self.size();
+        let max_len = Self::_max_len(size);
+
+        if self._tag_bits() == 0 {
+            return max_len;
+        }
+
+        max_len.min((Self::backing_bits() - TAG_BITS as u128 - META_BITS) / size)
+    }
+
+    /// Returns the data with the length set to `new_len` for items written after the current
+    /// ones. A value stored with `set_spare` is cleared so it can't corrupt the new items, but
+    /// the tag is kept.
+    fn _grow(&self, new_len: u128) -> u128 {
+        UintArray(self.normalize().0 | self._tag_bits())._set_len(new_len)
+    }
+
+    /// Puts the tag bits of `self` into `data` if the items of `data` leave room for them.
+    fn _keep_tag(&self, data: u128) -> Self {
+        if UintArray(data).spare_bits() < TAG_BITS as u128 {
+            return UintArray(data);
+        }
+
+        UintArray(data | self._tag_bits())
+    }
+
+    /// Creates a new UintArray with `tag` stored in the highest `TAG_BITS` bits, leaving the
+    /// items as is. Panics if the tag doesn't fit in `TAG_BITS` bits or the items reach into
+    /// the tag.
+    ///
+    /// Unlike a value stored with `set_spare`, the tag stays in place when items are appended,
+    /// inserted, popped, removed or shifted, when slicing with `take`, `skip` or `trim` and when
+    /// mapping the items with `clamp_each` or the `wrapping_*_scalar` methods. Methods that
+    /// build a new UintArray from scratch, such as `clear`, `normalize` or `partition`, drop it.
+    ///
+    /// The items can use at most `128 - TAG_BITS` bits including the length and size while the
+    /// UintArray has a tag. That leaves room for all 31 items of size 1 and 2, 28 items of size
+    /// 4, 14 items of size 8 and every item of the larger sizes. Appending or inserting past
+    /// that panics, and `try_append` and `try_insert` return `CapacityExceeded`. `shift_right`
+    /// and `shift_in` drop the items past the capacity as usual and then drop the tag too if
+    /// the items reach into it.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The tag to store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .with_tag(7)
+    ///     .extend(1..4);
+    ///
+    /// assert_eq!(7, ua.tag());
+    /// assert_eq!(vec![1, 2, 3], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn with_tag(&self, tag: u128) -> Self {
+        let tag_bits = TAG_BITS as u128;
+
+        if tag >> tag_bits != 0 {
+            Self::_panic(
+                "with_tag",
+                format_args!("tag={} does not fit in {} bits", tag, tag_bits),
+                self.size(),
+                self.len(),
+            );
+        }
+
+        if self.spare_bits() < tag_bits {
+            Self::_panic(
+                "with_tag",
+                format_args!("items reach into the tag"),
+                self.size(),
+                self.len(),
+            );
+        }
+
+        let offset = Self::backing_bits() - tag_bits;
+        UintArray(self.0 & Self::_mask(offset) | tag << offset)
+    }
+
+    /// Whether two UintArrays have the same size, and therefore the same capacity.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The UintArray to compare with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let a = UintArray::new_size(4).append(1);
+    /// let b = UintArray::new_size(4);
+    ///
+    /// assert!(a.same_shape(&b));
+    /// assert!(!a.same_shape(&UintArray::new_size(8)));
+    /// ```
+    #[inline]
+    pub fn same_shape(&self, other: &UintArray) -> bool {
+        self.size() == other.size()
+    }
+
+    /// Whether two UintArrays have the same items when trailing zero items are ignored.
+    /// The sizes of the UintArrays may differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The UintArray to compare with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let a = UintArray::new::<u8>().extend(vec![1, 2]);
+    /// let b = UintArray::new::<u8>().extend(vec![1, 2, 0, 0]);
+    ///
+    /// assert!(a.eq_ignore_trailing_zeros(&b));
+    /// ```
+    pub fn eq_ignore_trailing_zeros(&self, other: &UintArray) -> bool {
+        let len = self.len() - self.trailing_zeros_count();
+        let other_len = other.len() - other.trailing_zeros_count();
+
+        len == other_len
+            && self
+                .into_iter()
+                .take(len as usize)
+                .eq(other.into_iter().take(len as usize))
+    }
+
+    /// Get the item at position `pos`. First item is at `pos = 0` (i.e. it's zero-indexed).
+    /// Returns None if out of bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the item to get.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .append(2)
+    ///     .append(4)
+    ///     .append(8);
+    ///
+    /// assert_eq!(Some(4), ua.at(1))
+    /// ```
+    pub fn at(&self, pos: u128) -> Option<u128> {
+        if pos >= self.len() {
+            None
+        } else {
+            let size = self.size();
+            let offset = size * pos + META_BITS;
+            self._at(size, offset)
+        }
+    }
+
+    /// Get the item at position `pos` along with its bit offset in the uint.
+    /// Returns None if out of bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the item to get.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .append(2)
+    ///     .append(4);
+    ///
+    /// assert_eq!(Some((4, 12)), ua.at_with_offset(1))
+    /// ```
+    pub fn at_with_offset(&self, pos: u128) -> Option<(u128, u128)> {
+        if pos >= self.len() {
+            None
+        } else {
+            let size = self.size();
+            let offset = size * pos + META_BITS;
+            self._at(size, offset).map(|item| (item, offset))
+        }
+    }
+
+    /// Get the item at position `pos` as a `u8`. Returns None if out of bounds.
+    /// Panics if the size of the UintArray isn't 8.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the item to get.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>().extend(1..4);
+    ///
+    /// assert_eq!(Some(2u8), ua.at_u8(1));
+    /// ```
+    pub fn at_u8(&self, pos: u128) -> Option<u8> {
+        self._at_sized("at_u8", 8, pos).map(|x| x as u8)
+    }
+
+    /// Get the item at position `pos` as a `u16`. Returns None if out of bounds.
+    /// Panics if the size of the UintArray isn't 16.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the item to get.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u16>().extend(1..4);
+    ///
+    /// assert_eq!(Some(2u16), ua.at_u16(1));
+    /// ```
+    pub fn at_u16(&self, pos: u128) -> Option<u16> {
+        self._at_sized("at_u16", 16, pos).map(|x| x as u16)
+    }
+
+    /// Get the item at position `pos` as a `u32`. Returns None if out of bounds.
+    /// Panics if the size of the UintArray isn't 32.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the item to get.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u32>().extend(1..3);
+    ///
+    /// assert_eq!(Some(2u32), ua.at_u32(1));
+    /// ```
+    pub fn at_u32(&self, pos: u128) -> Option<u32> {
+        self._at_sized("at_u32", 32, pos).map(|x| x as u32)
+    }
+
+    /// Get the item at position `pos` as a `u64`. Returns None if out of bounds.
+    /// Panics if the size of the UintArray isn't 64.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the item to get.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u64>().append(1);
+    ///
+    /// assert_eq!(Some(1u64), ua.at_u64(0));
+    /// ```
+    pub fn at_u64(&self, pos: u128) -> Option<u64> {
+        self._at_sized("at_u64", 64, pos).map(|x| x as u64)
+    }
+
+    /// Get the item at position `pos`, panicking in `method` if the size isn't `size`.
+    fn _at_sized(&self, method: &str, size: u128, pos: u128) -> Option<u128> {
+        if self._check_size(size).is_err() {
+            Self::_panic(
+                method,
+                format_args!("size is not {}", size),
+                self.size(),
+                self.len(),
+            );
+        }
+
+        self.at(pos)
+    }
+
+    /// Get the item at a given position, disregarding whether it exists.
+    #[inline]
+    fn _at(&self, size: u128, offset: u128) -> Option<u128> {
+        Some((Self::_mask(size) << offset & self.0) >> offset)
+    }
+
+    /// Iterates the bit offsets of the items in the UintArray, i.e. where in the uint each
+    /// item starts. Useful for masking the uint directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua.extend(1..4);
+    ///
+    /// assert_eq!(vec![8, 12, 16], ua.slot_offsets().collect::<Vec<u128>>());
+    /// ```
+    pub fn slot_offsets(&self) -> impl Iterator<Item = u128> {
+        let size = self.size();
+        (0..self.len()).map(move |i| i * size + META_BITS)
+    }
+
+    /// Iterates the bit offsets of the items in the UintArray from the last item to the first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua.extend(1..4);
+    ///
+    /// assert_eq!(vec![16, 12, 8], ua.offsets_rev().collect::<Vec<u128>>());
+    /// ```
+    pub fn offsets_rev(&self) -> impl Iterator<Item = u128> {
+        let size = self.size();
+        (0..self.len()).rev().map(move |i| i * size + META_BITS)
+    }
+
+    /// Iterates the bit masks of the items in the UintArray, i.e. which bits of the uint each
+    /// item occupies. Combine with `raw()` to extract items directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua.extend(1..3);
+    ///
+    /// assert_eq!(vec![0xf00, 0xf000], ua.masks().collect::<Vec<u128>>());
+    /// assert_eq!(2 << 12, ua.raw() & ua.masks().nth(1).unwrap());
+    /// ```
+    pub fn masks(&self) -> impl Iterator<Item = u128> {
+        let mask = Self::_mask(self.size());
+        self.slot_offsets().map(move |offset| mask << offset)
+    }
+
+    /// Gets the bit offset the next appended item would start at, or None if the UintArray is
+    /// full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua.extend(1..3);
+    ///
+    /// assert_eq!(Some(16), ua.would_append_at());
+    /// assert_eq!(None, UintArray::new::<u64>().append(1).would_append_at());
+    /// ```
+    pub fn would_append_at(&self) -> Option<u128> {
+        let len = self.len();
+
+        if len >= Self::_max_len(self.size()) {
+            None
+        } else {
+            Some(len * self.size() + META_BITS)
+        }
+    }
+
+    /// Creates a new UintArray with the given item appended to the end.
+    /// Panics if appending would exceed capacity or if the item doesn't fit in the UintArray size.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to append.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .append(1)
+    ///     .append(2)
+    ///     .append(3);
+    ///
+    /// assert_eq!(Some(1), ua.at(0));
+    /// assert_eq!(3, ua.len());
+    /// ```
+    pub fn append(&self, item: u128) -> Self {
+        let len = self.len();
+        let size = self.size();
+
+        Self::_check_insert_panic("append", size, len, 1, item);
+
+        self._append("append", item, size, len)
+    }
+
+    /// Creates a new UintArray with the given item appended to the end `n` times.
+    /// Panics if appending would exceed capacity or if the item doesn't fit in the UintArray size.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to append.
+    /// * `n` - How many times to append the item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua.append_repeat(7, 3);
+    ///
+    /// assert_eq!(3, ua.len());
+    /// assert_eq!(Some(7), ua.at(2));
+    /// ```
+    pub fn append_repeat(&self, item: u128, n: u128) -> Self {
+        if n == 0 {
+            return *self;
+        }
+
+        let len = self.len();
+        let size = self.size();
+
+        // Checking once is enough since the items are all the same.
+        Self::_check_insert_panic("append_repeat", size, len, n, item);
+        Self::_check_tag_panic("append_repeat", size, len, n, self._len_limit());
+
+        let mut items: u128 = 0;
+        for i in 0..n {
+            items |= item << (i * size);
+        }
+
+        UintArray(self._grow(len + n) | items << (len * size + META_BITS))
+    }
+
+    /// Creates a new UintArray with the given item appended to the end, first repacking the
+    /// items into the smallest larger size the item fits in if it doesn't fit the current size.
+    /// Panics if appending would exceed capacity or if the item doesn't fit in any size.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to append.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .append(1)
+    ///     .push_growing(20);
+    ///
+    /// assert_eq!(8, ua.size());
+    /// assert_eq!(Some(20), ua.at(1));
+    /// ```
+    pub fn push_growing(&self, item: u128) -> Self {
+        let mut size = self.size();
+
+        while size < MAX_ELEMENT_SIZE as u128 && Self::_mask(size) & item != item {
+            size *= 2;
+        }
+
+        let ua = if size == self.size() {
+            *self
+        } else {
+            Self::new_size(size as usize)._extend("push_growing", *self)
+        };
+
+        let len = ua.len();
+        Self::_check_insert_panic("push_growing", size, len, 1, item);
+
+        ua._append("push_growing", item, size, len)
+    }
+
+    /// Creates a new UintArray with the same items packed in a different size.
+    /// Returns an error if the size is invalid, if an item doesn't fit in the new size or if the
+    /// items exceed the capacity of the new size.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_size` - The size in bits to repack the items in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::{UintArray, UintArrayError};
+    /// let ua = UintArray::new::<u8>().extend(vec![1, 15]);
+    ///
+    /// assert_eq!(Ok(vec![1, 15]), ua.resize_elements(4).map(|ua| ua.into_iter().collect()));
+    /// assert_eq!(Err(UintArrayError::ItemTooLarge), ua.resize_elements(2).map(|ua| ua.raw()));
+    /// ```
+    pub fn resize_elements(&self, new_size: usize) -> Result<Self, UintArrayError> {
+        Self::_check_size_valid(new_size)?;
+
+        let max = self.into_iter().max().unwrap_or(0);
+
+        Self::_check_insert(new_size as u128, 0, self.len(), max)?;
+
+        Ok(Self::new_size(new_size).extend(*self))
+    }
+
+    /// Creates a new UintArray with the given item appended to the end.
+    /// Returns an error if appending would exceed capacity or if the item doesn't fit in the
+    /// UintArray size. The capacity ends where the items would reach into a tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to append.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::{UintArray, UintArrayError};
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// assert_eq!(Some(1), ua.try_append(1).unwrap().at(0));
+    /// assert_eq!(Err(UintArrayError::ItemTooLarge), ua.try_append(16).map(|ua| ua.raw()));
+    /// ```
+    pub fn try_append(&self, item: u128) -> Result<Self, UintArrayError> {
+        let len = self.len();
+        let size = self.size();
+
+        Self::_check_insert(size, len, 1, item)?;
+
+        if len >= self._len_limit() {
+            return Err(UintArrayError::CapacityExceeded);
+        }
+
+        Ok(self._append("try_append", item, size, len))
+    }
+
+    /// Appends an item that is known to fit, panicking in `method` if it would reach into the
+    /// tag.
+    #[inline]
+    fn _append(&self, method: &str, item: u128, size: u128, len: u128) -> Self {
+        Self::_check_tag_panic(method, size, len, 1, self._len_limit());
+
+        UintArray(self._grow(len + 1) | item << (len * size + META_BITS))
+    }
+
+    /// Creates a new UintArray with the given item inserted at the given position.
+    /// Panics if appending would exceed capacity or if the item doesn't fit in the UintArray size.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - The position to insert the item at.
+    /// * `item` - The item to insert.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .append(1)
+    ///     .append(2)
+    ///     .insert(1, 3);
+    ///
+    /// assert_eq!(Some(3), ua.at(1));
+    /// ```
+    pub fn insert(&self, pos: u128, item: u128) -> Self {
+        let len = self.len();
+        let size = self.size();
+        Self::_check_insert_panic("insert", size, len, 1, item);
+
+        if pos > len {
+            return self._append("insert", item, size, len);
+        }
+
+        match self._insert(pos, item, size, len) {
+            Some(ua) => ua,
+            None => Self::_panic(
+                "insert",
+                format_args!("shifting would overflow the uint"),
+                size,
+                len,
+            ),
+        }
+    }
+
+    /// Inserts the item at a position within the length, or returns None if bits after the
+    /// last item, such as a value stored with `set_spare`, would be shifted out of the uint.
+    fn _insert(&self, pos: u128, item: u128, size: u128, len: u128) -> Option<Self> {
+        let tag = self._tag_bits();
+        let data = UintArray(self.0 & !tag);
+        let offset = pos * size + META_BITS;
+        let pos_mask = Self::_mask(offset);
+
+        // The shift below silently drops any bits pushed past the top of the uint.
+        if (data.0 & !pos_mask).leading_zeros() < size as u32 {
+            return None;
+        }
+
+        // Pushes everything after the offset off by `size` and inserts the item inbetween.
+        //
+        // If offset is at 4, the `size` is 2 and the new item is AA,
+        // it will do the following:
+        //
+        // 000011110000 -> 0000    0000 -> 001111  0000 -> 001111AA0000
+        //                   1111                AA
+        let ua = UintArray(
+            data._set_len(len + 1) & pos_mask | (data.0 & !pos_mask) << size | item << offset,
+        );
+
+        // The tag is only put back if the items don't reach into it.
+        if tag != 0 && ua.spare_bits() < TAG_BITS as u128 {
+            return None;
+        }
+
+        Some(UintArray(ua.0 | tag))
+    }
+
+    /// Creates a new UintArray with the given item inserted after all smaller or equal items,
+    /// keeping a sorted UintArray sorted.
+    /// Panics if inserting would exceed capacity or if the item doesn't fit in the UintArray size.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The item to insert.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(vec![1, 2, 4])
+    ///     .insert_sorted(3);
+    ///
+    /// assert_eq!(vec![1, 2, 3, 4], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn insert_sorted(&self, item: u128) -> Self {
+        let size = self.size();
+        let mut low = 0;
+        let mut high = self.len();
+
+        // Binary search for the first item greater than `item`.
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            if self._at(size, mid * size + META_BITS) <= Some(item) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        self.insert(low, item)
+    }
+
+    /// Creates a new UintArray with the given item inserted at the given position.
+    /// Returns an error if inserting would exceed capacity, if the item doesn't fit in the
+    /// UintArray size or if the position is after the end of the UintArray.
+    /// Inserting also exceeds capacity when it would shift spare bits out of the uint.
+    ///
+    /// Unlike `insert`, a position greater than the length is not treated as appending.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - The position to insert the item at.
+    /// * `item` - The item to insert.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::{UintArray, UintArrayError};
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .append(1)
+    ///     .append(2);
+    ///
+    /// assert_eq!(Some(3), ua.try_insert(1, 3).unwrap().at(1));
+    /// assert_eq!(Err(UintArrayError::OutOfBounds), ua.try_insert(3, 3).map(|ua| ua.raw()));
+    /// ```
+    pub fn try_insert(&self, pos: u128, item: u128) -> Result<Self, UintArrayError> {
+        let len = self.len();
+
+        if pos > len {
+            return Err(UintArrayError::OutOfBounds);
+        }
+
+        let size = self.size();
+
+        Self::_check_insert(size, len, 1, item)?;
+
+        self._insert(pos, item, size, len)
+            .ok_or(UintArrayError::CapacityExceeded)
+    }
+
+    /// Creates a new UintArray with the items at the given positions replaced, all in one pass.
+    /// Returns an error if a position is out of bounds or a value doesn't fit in the UintArray
+    /// size. Later pairs win if a position is given more than once.
+    ///
+    /// # Arguments
+    ///
+    /// * `positions` - Pairs of the position to replace and the value to replace it with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::{UintArray, UintArrayError};
+    /// let ua = UintArray::new::<u8>().extend(1..4);
+    ///
+    /// let set = ua.set_many(&[(0, 7), (2, 9)]).unwrap();
+    ///
+    /// assert_eq!(vec![7, 2, 9], set.into_iter().collect::<Vec<u128>>());
+    /// assert_eq!(Err(UintArrayError::OutOfBounds), ua.set_many(&[(3, 1)]).map(|ua| ua.raw()));
+    /// ```
+    pub fn set_many(&self, positions: &[(u128, u128)]) -> Result<Self, UintArrayError> {
+        let len = self.len();
+        let size = self.size();
+        let mask = Self::_mask(size);
+        let mut data = self.0;
+
+        for &(pos, value) in positions {
+            if pos >= len {
+                return Err(UintArrayError::OutOfBounds);
+            }
+
+            if mask & value != value {
+                return Err(UintArrayError::ItemTooLarge);
+            }
+
+            let offset = pos * size + META_BITS;
+            data = data & !(mask << offset) | value << offset;
+        }
+
+        Ok(UintArray(data))
+    }
+
+    /// Extends the UintArray with the values of the iterator.
+    /// Panics if inserting would exceed the capacity or an item is greater than size.
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - Iterator of items to append.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(1..4);
+    ///
+    /// assert_eq!(Some(2), ua.at(1));
+    /// assert_eq!(3, ua.len());
+    /// ```
+    #[must_use = "this returns a new UintArray, use `Extend::extend` to extend in place"]
     pub fn extend<T: IntoIterator<Item = u128>>(&self, iter: T) -> Self {
+        self._extend("extend", iter)
+    }
+
+    /// Extends the UintArray with the values of the iterator, panicking in `method` if
+    /// inserting would exceed the capacity or an item is greater than size.
+    fn _extend<T: IntoIterator<Item = u128>>(&self, method: &str, iter: T) -> Self {
+        let len = self.len();
+        let size = self.size();
+
+        // A corrupt size would otherwise pack the items wrongly without panicking.
+        if Self::_check_size_valid(size as usize).is_err() {
+            Self::_panic(
+                method,
+                format_args!("{}", UintArrayError::InvalidSize),
+                size,
+                len,
+            );
+        }
+
+        let mut iter_len: u128 = 0;
+        let mut max: u128 = 0;
+        let mut items: u128 = 0;
+
+        for i in iter {
+            iter_len += 1;
+
+            if i > max {
+                max = i;
+            }
+
+            Self::_check_capacity_panic(method, size, len, iter_len);
+
+            // Everything is put in sequence in `items`.
+            items |= i << ((iter_len - 1) * size);
+        }
+
+        if iter_len == 0 {
+            return *self;
+        }
+
+        let new_len = len + iter_len;
+
+        // We got the max, so we only need to check once.
+        Self::_check_insert_panic(method, size, len, iter_len, max);
+        Self::_check_tag_panic(method, size, len, iter_len, self._len_limit());
+
+        // Add `items` to the end.
+        UintArray(self._grow(new_len) | items << (size * len + META_BITS))
+    }
+
+    /// Extends the UintArray with the values of the iterator until it's full, ignoring the rest.
+    /// A UintArray with size 1 or 2 is full at 31 items, the largest length, and a UintArray
+    /// with a tag is full before its items reach into the tag.
+    /// Panics if an item that is appended is greater than size.
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - Iterator of items to append.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u16>();
+    ///
+    /// let ua = ua.extend_saturating(0..100);
+    ///
+    /// assert_eq!(ua.cap(), ua.len());
+    /// ```
+    pub fn extend_saturating<T: IntoIterator<Item = u128>>(&self, iter: T) -> Self {
+        // Sizes 1 and 2 are full at 31 items, before reaching their capacity, and a tag makes
+        // the UintArray full before its items reach into it.
+        let room = self._len_limit().saturating_sub(self.len());
+        self._extend("extend_saturating", iter.into_iter().take(room as usize))
+    }
+
+    /// Extends the UintArray with the items of another UintArray.
+    /// Panics if inserting would exceed the capacity or an item is greater than size.
+    ///
+    /// When both UintArrays have the same size the items are copied all at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The UintArray whose items to append.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let a = UintArray::new::<u8>().extend(1..3);
+    /// let b = UintArray::new::<u8>().extend(3..5);
+    ///
+    /// let ua = a.extend_array(&b);
+    ///
+    /// assert_eq!(vec![1, 2, 3, 4], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn extend_array(&self, other: &UintArray) -> Self {
+        if !self.same_shape(other) {
+            return self._extend("extend_array", *other);
+        }
+
+        let size = self.size();
+        let len = self.len();
+        let other_len = other.len();
+
+        if other_len == 0 {
+            return *self;
+        }
+
+        Self::_check_capacity_panic("extend_array", size, len, other_len);
+        Self::_check_tag_panic("extend_array", size, len, other_len, self._len_limit());
+
+        let items = other.0 >> META_BITS & Self::_mask(other_len * size);
+        UintArray(self._grow(len + other_len) | items << (len * size + META_BITS))
+    }
+
+    /// Creates a new UintArray by appending items in place inside a closure.
+    /// The length is only written once the closure returns, which saves rebuilding the
+    /// UintArray for every item.
+    /// Panics if appending would exceed capacity or if an item doesn't fit in the UintArray size.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Closure that appends items through the builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.with(|b| {
+    ///     b.append(1).append(2).extend(3..5);
+    /// });
+    ///
+    /// assert_eq!(vec![1, 2, 3, 4], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn with<F>(&self, f: F) -> Self
+    where
+        F: FnOnce(&mut UintArrayBuilder),
+    {
+        let mut builder = UintArrayBuilder::new(*self);
+        f(&mut builder);
+        builder.finish()
+    }
+
+    /// Checks whether every item of the iterator could be appended without exceeding the
+    /// capacity or the UintArray size.
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - Iterator of items to check.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// assert!(ua.can_extend(1..4));
+    /// assert!(!ua.can_extend(vec![256]));
+    /// ```
+    pub fn can_extend<T: IntoIterator<Item = u128>>(&self, iter: T) -> bool {
+        let (count, max) = iter
+            .into_iter()
+            .fold((0, 0), |(count, max), i| (count + 1, max.max(i)));
+
+        Self::_check_insert(self.size(), self.len(), count, max).is_ok()
+    }
+
+    /// Checks whether `additional` more items could be appended without exceeding the capacity.
+    /// A UintArray with size 1 or 2 can hold at most 31 items, the largest length, and items
+    /// can't be appended into a tag stored with `with_tag`.
+    ///
+    /// # Arguments
+    ///
+    /// * `additional` - Number of items to check for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u32>().append(1);
+    ///
+    /// assert!(ua.can_fit(2));
+    /// assert!(!ua.can_fit(3));
+    /// ```
+    pub fn can_fit(&self, additional: u128) -> bool {
+        self.len().saturating_add(additional) <= self._len_limit()
+    }
+
+    /// Returns how many more times `item` could be appended before the UintArray is full.
+    /// A UintArray with size 1 or 2 is full at 31 items, the largest length, and a UintArray
+    /// with a tag is full before its items reach into the tag.
+    /// Returns an error if the item doesn't fit in the UintArray size.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The item to check for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::{UintArray, UintArrayError};
+    /// let ua = UintArray::new_size(4).extend(1..4);
+    ///
+    /// assert_eq!(Ok(27), ua.fits_count(3));
+    /// assert_eq!(Err(UintArrayError::ItemTooLarge), ua.fits_count(99));
+    /// ```
+    pub fn fits_count(&self, item: u128) -> Result<u128, UintArrayError> {
+        Self::_check_insert(self.size(), 0, 0, item)?;
+
+        Ok(self._len_limit().saturating_sub(self.len()))
+    }
+
+    /// Iterates the items starting at position `start`.
+    /// The iterator is empty if `start` is beyond the length of the UintArray.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Position of the first item to yield.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(1..5);
+    ///
+    /// assert_eq!(vec![3, 4], ua.iter_from(2).collect::<Vec<u128>>());
+    /// ```
+    pub fn iter_from(&self, start: u128) -> UintArrayIterator {
+        UintArrayIterator {
+            ua: *self,
+            index: start,
+        }
+    }
+
+    /// Iterates the last `n` items in order.
+    /// Iterates all items if `n` is greater than the length.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - How many items to yield.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(1..5);
+    ///
+    /// assert_eq!(vec![3, 4], ua.tail(2).collect::<Vec<u128>>());
+    /// ```
+    pub fn tail(&self, n: u128) -> impl Iterator<Item = u128> {
+        let len = self.len();
+        self.iter_from(len - n.min(len))
+    }
+
+    /// Iterates the items of a UintArray with size 1 as booleans.
+    /// Panics if the size isn't 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(1);
+    ///
+    /// let ua = ua.extend(vec![1, 0, 1]);
+    ///
+    /// assert_eq!(vec![true, false, true], ua.bits().collect::<Vec<bool>>());
+    /// ```
+    pub fn bits(&self) -> impl Iterator<Item = bool> {
+        if self.size() != 1 {
+            Self::_panic(
+                "bits",
+                format_args!("size is not 1"),
+                self.size(),
+                self.len(),
+            );
+        }
+
+        self.into_iter().map(|x| x == 1)
+    }
+
+    /// Iterates the items in positions `start..end` without creating a new UintArray.
+    /// Positions beyond the length of the UintArray are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Position of the first item to yield.
+    /// * `end` - Position after the last item to yield.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(1..5);
+    ///
+    /// assert_eq!(vec![2, 3], ua.range_iter(1, 3).collect::<Vec<u128>>());
+    /// ```
+    pub fn range_iter(&self, start: u128, end: u128) -> impl Iterator<Item = u128> {
+        let ua = *self;
+        let size = self.size();
+        let end = end.min(self.len());
+
+        (start..end).map(move |i| (ua.0 >> (i * size + META_BITS)) & Self::_mask(size))
+    }
+
+    /// Merges two sorted UintArrays into a new sorted UintArray.
+    /// Panics if the sizes differ or the merged items would exceed capacity.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The sorted UintArray to merge with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let a = UintArray::new::<u8>().extend(vec![1, 3]);
+    /// let b = UintArray::new::<u8>().extend(vec![2, 4]);
+    ///
+    /// let ua = a.merge_sorted(&b);
+    ///
+    /// assert_eq!(vec![1, 2, 3, 4], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn merge_sorted(&self, other: &UintArray) -> Self {
+        self._check_same_size_panic("merge_sorted", "other", other);
+
+        let mut a = self.into_iter().peekable();
+        let mut b = other.into_iter().peekable();
+        let mut merged = Vec::new();
+
+        // Take the smallest of the two next items until both are exhausted.
+        loop {
+            let item = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if y < x => b.next(),
+                (Some(_), _) => a.next(),
+                (None, Some(_)) => b.next(),
+                (None, None) => break,
+            };
+            merged.extend(item);
+        }
+
+        self.clear()._extend("merge_sorted", merged)
+    }
+
+    /// Creates a new UintArray alternating the items of two UintArrays, starting with this one.
+    /// The remaining items of the longest UintArray are appended at the end.
+    /// Panics if the sizes differ or the items would exceed capacity.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The UintArray to alternate with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let a = UintArray::new::<u8>().extend(vec![1, 3]);
+    /// let b = UintArray::new::<u8>().extend(vec![2, 4]);
+    ///
+    /// let ua = a.interleave(&b);
+    ///
+    /// assert_eq!(vec![1, 2, 3, 4], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn interleave(&self, other: &UintArray) -> Self {
+        self._check_same_size_panic("interleave", "other", other);
+
+        let len = self.len().max(other.len());
+
+        self.clear()._extend(
+            "interleave",
+            (0..len).flat_map(|i| self.at(i).into_iter().chain(other.at(i))),
+        )
+    }
+
+    /// Splits the UintArray into the items at even positions and the items at odd positions.
+    /// The inverse of `interleave`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>().extend(1..5);
+    ///
+    /// let (even, odd) = ua.deinterleave();
+    ///
+    /// assert_eq!(vec![1, 3], even.into_iter().collect::<Vec<u128>>());
+    /// assert_eq!(vec![2, 4], odd.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn deinterleave(&self) -> (Self, Self) {
+        let size = self.size();
+        let mut even = self.clear();
+        let mut odd = self.clear();
+        let mut pos = 0;
+
+        self._apply(self.len(), size, |x| {
+            let ua = if pos % 2 == 0 { &mut even } else { &mut odd };
+            *ua = ua._append("deinterleave", x, size, ua.len());
+            pos += 1;
+        });
+
+        (even, odd)
+    }
+
+    /// Iterates pairs of items from two UintArrays, stopping at the end of the shortest.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The UintArray to pair items with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let a = UintArray::new::<u8>().extend(1..4);
+    /// let b = UintArray::new::<u8>().extend(4..6);
+    ///
+    /// assert_eq!(vec![(1, 4), (2, 5)], a.zip(&b).collect::<Vec<(u128, u128)>>());
+    /// ```
+    pub fn zip<'a>(&'a self, other: &'a UintArray) -> impl Iterator<Item = (u128, u128)> + 'a {
+        self.into_iter().zip(*other)
+    }
+
+    /// Creates a new UintArray with the smallest of each pair of items from two UintArrays,
+    /// stopping at the end of the shortest.
+    /// Panics if the sizes differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The UintArray to compare items with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let a = UintArray::new::<u8>().extend(vec![1, 4, 2]);
+    /// let b = UintArray::new::<u8>().extend(vec![3, 2, 5]);
+    ///
+    /// let ua = a.zip_min(&b);
+    ///
+    /// assert_eq!(vec![1, 2, 2], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn zip_min(&self, other: &UintArray) -> Self {
+        self._zip_with("zip_min", other, |a, b| a.min(b))
+    }
+
+    /// Creates a new UintArray with the largest of each pair of items from two UintArrays,
+    /// stopping at the end of the shortest.
+    /// Panics if the sizes differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The UintArray to compare items with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let a = UintArray::new::<u8>().extend(vec![1, 4, 2]);
+    /// let b = UintArray::new::<u8>().extend(vec![3, 2, 5]);
+    ///
+    /// let ua = a.zip_max(&b);
+    ///
+    /// assert_eq!(vec![3, 4, 5], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn zip_max(&self, other: &UintArray) -> Self {
+        self._zip_with("zip_max", other, |a, b| a.max(b))
+    }
+
+    /// Creates a new UintArray by applying a function to each pair of items from two
+    /// UintArrays of the same size, panicking in `method` if the sizes differ. The function
+    /// must return values that fit in the size.
+    fn _zip_with<F>(&self, method: &str, other: &UintArray, f: F) -> Self
+    where
+        F: Fn(u128, u128) -> u128,
+    {
+        self._check_same_size_panic(method, "other", other);
+
+        self.clear()
+            ._extend(method, self.zip(other).map(|(a, b)| f(a, b)))
+    }
+
+    /// Creates a new UintArray with the first `n` items.
+    /// Takes all items if `n` is greater than the length.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - How many items to take.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(1..5)
+    ///     .take(2);
+    ///
+    /// assert_eq!(vec![1, 2], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn take(&self, n: u128) -> Self {
+        let len = self.len();
+        self._slice(self.size(), 0, n.min(len))
+    }
+
+    /// Creates a new UintArray without the first `n` items.
+    /// Skips all items if `n` is greater than the length.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - How many items to skip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(1..5)
+    ///     .skip(2);
+    ///
+    /// assert_eq!(vec![3, 4], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn skip(&self, n: u128) -> Self {
+        let len = self.len();
+        self._slice(self.size(), n.min(len), len)
+    }
+
+    /// Creates a new UintArray with all items moved `n` positions towards the start,
+    /// dropping the first `n` items.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - How many positions to move the items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(1..4)
+    ///     .shift_left(1);
+    ///
+    /// assert_eq!(vec![2, 3], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn shift_left(&self, n: u128) -> Self {
+        self.skip(n)
+    }
+
+    /// Creates a new UintArray with all items moved `n` positions towards the end and zeros in
+    /// the first `n` positions. Items moved beyond the capacity are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - How many positions to move the items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(1..4)
+    ///     .shift_right(1);
+    ///
+    /// assert_eq!(vec![0, 1, 2, 3], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn shift_right(&self, n: u128) -> Self {
+        let size = self.size();
+        let new_len = self.len().saturating_add(n).min(Self::_max_len(size));
+        let n = n.min(new_len);
+        let kept = new_len - n;
+
+        let data = self.clear()._set_len(new_len);
+
+        if kept == 0 {
+            return self._keep_tag(data);
+        }
+
+        let items = self.0 >> META_BITS & Self::_mask(kept * size);
+        self._keep_tag(data | items << (n * size + META_BITS))
+    }
+
+    /// Creates a new UintArray with `item` inserted at the start, moving all items one position
+    /// towards the end like a shift register. If the UintArray is full, the last item is
+    /// dropped and returned as the carry, otherwise the carry is 0.
+    /// Panics if the item doesn't fit in the UintArray size.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to insert at the start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u32>();
+    ///
+    /// let (ua, carry) = ua
+    ///     .extend(1..4)
+    ///     .shift_in(9);
+    ///
+    /// assert_eq!(3, carry);
+    /// assert_eq!(vec![9, 1, 2], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn shift_in(&self, item: u128) -> (Self, u128) {
+        let len = self.len();
+        let size = self.size();
+
+        // Nothing is added, since the last item is dropped when full.
+        Self::_check_insert_panic("shift_in", size, len, 0, item);
+
+        let carry = if len == Self::_max_len(size) {
+            self._at(size, (len - 1) * size + META_BITS).unwrap_or(0)
+        } else {
+            0
+        };
+
+        // The tag is kept by shift_right, so only the first item has to be added.
+        (UintArray(self.shift_right(1).0 | item << META_BITS), carry)
+    }
+
+    /// Creates a new UintArray without the zero items at the start and end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(vec![0, 1, 2, 0, 0])
+    ///     .trim();
+    ///
+    /// assert_eq!(vec![1, 2], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn trim(&self) -> Self {
+        let start = self.leading_zeros_count();
+        let end = self.len() - self.trailing_zeros_count();
+        self._slice(self.size(), start, end)
+    }
+
+    /// Creates a new UintArray with the items in positions `start..end`.
+    /// Both positions must be within the length of the UintArray.
+    fn _slice(&self, size: u128, start: u128, end: u128) -> Self {
+        let tag = self._tag_bits();
+
+        if start >= end {
+            return UintArray(self.clear().0 | tag);
+        }
+
+        let len = end - start;
+        let items = self.0 >> (start * size + META_BITS) & Self::_mask(len * size);
+
+        UintArray(self.clear()._set_len(len) | items << META_BITS | tag)
+    }
+
+    /// Iterates the items in chunks of `n` items, starting from the end of the UintArray.
+    /// The last chunk holds the remaining items if the length isn't divisible by `n`.
+    /// Panics if `n` is 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of items in each chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(1..4);
+    /// let mut chunks = ua.rchunks(2);
+    ///
+    /// assert_eq!(Some(vec![2, 3]), chunks.next());
+    /// assert_eq!(Some(vec![1]), chunks.next());
+    /// assert_eq!(None, chunks.next());
+    /// ```
+    pub fn rchunks(&self, n: u128) -> impl Iterator<Item = Vec<u128>> {
+        if n == 0 {
+            Self::_panic(
+                "rchunks",
+                format_args!("chunk size must be non-zero"),
+                self.size(),
+                self.len(),
+            );
+        }
+
+        let ua = *self;
+        let mut end = self.len();
+
+        std::iter::from_fn(move || {
+            if end == 0 {
+                return None;
+            }
+
+            let start = end.saturating_sub(n);
+            let chunk = ua.range_iter(start, end).collect();
+            end = start;
+
+            Some(chunk)
+        })
+    }
+
+    /// Clears all values from the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .append(15)
+    ///     .append(14)
+    ///     .clear();
+    ///
+    /// assert_eq!(0, ua.len());
+    /// assert_eq!(8, ua.size());
+    /// ```
+    #[inline]
+    pub fn clear(&self) -> Self {
+        UintArray(self.0 & SIZE_MASK)
+    }
+
+    /// Returns an empty UintArray with size 1.
+    /// Unlike `clear`, this doesn't preserve the size of the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .append(15)
+    ///     .reset();
+    ///
+    /// assert_eq!(0, ua.len());
+    /// assert_eq!(1, ua.size());
+    /// ```
+    #[inline]
+    pub fn reset(&self) -> Self {
+        Self::new_size(1)
+    }
+
+    /// Removes the first occurrence of an item from the UintArray.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The item to remove.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(1..4)
+    ///     .remove(2);
+    ///
+    /// assert_eq!(Some(3), ua.at(1));
+    /// assert_eq!(2, ua.len());
+    /// ```
+    pub fn remove(&self, item: u128) -> Self {
+        let len = self.len();
+        let size = self.size();
+
+        let pos = self._index(item, len, size);
+
+        match pos {
+            Some(i) => self.pop(i).0,
+            None => *self,
+        }
+    }
+
+    /// Removes an item from the UintArray at a given index and returns it and the UintArray.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - The index of the item to remove and return.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(1..4);
+    ///
+    /// let (ua, item) = ua.pop(1);
+    ///
+    /// assert_eq!(Some(2), item);
+    /// ```
+    pub fn pop(&self, pos: u128) -> (Self, Option<u128>) {
+        let len = self.len();
+        let size = self.size();
+
+        if pos >= len {
+            return (*self, None);
+        }
+
+        let tag = self._tag_bits();
+        let data = UintArray(self.0 & !tag);
+        let offset = pos * size + META_BITS;
+        let pos_mask = Self::_mask(offset);
+
+        (
+            // Move everything after `pos` down by `size`, discarding any overlap and effectivly
+            // removing the item in question.
+            //
+            // If the offset is at the transition from 0's to 1's and the size is 2,
+            // it will do the following:
+            //
+            // 1111110000 ->     0000 -> 11110000
+            //               111111
+            //
+            // The tag is taken out first so it isn't moved down with the items.
+            UintArray(
+                data._set_len(len - 1) & pos_mask | (data.0 & !pos_mask) >> size & !pos_mask | tag,
+            ),
+            self._at(size, offset),
+        )
+    }
+
+    /// Removes the last item from the UintArray and returns it and the UintArray.
+    /// Cheaper than `pop(len - 1)` since no items have to be moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(1..4);
+    ///
+    /// let (ua, item) = ua.pop_back();
+    ///
+    /// assert_eq!(Some(3), item);
+    /// assert_eq!(vec![1, 2], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn pop_back(&self) -> (Self, Option<u128>) {
+        let mut ua = *self;
+        let item = ua.pop_back_mut();
+        (ua, item)
+    }
+
+    /// Removes the first item from the UintArray and returns it and the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(1..4);
+    ///
+    /// let (ua, item) = ua.pop_front();
+    ///
+    /// assert_eq!(Some(1), item);
+    /// assert_eq!(vec![2, 3], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn pop_front(&self) -> (Self, Option<u128>) {
+        self.pop(0)
+    }
+
+    /// Appends an item to the end of the UintArray in place.
+    /// Panics if appending would exceed capacity or if the item doesn't fit in the UintArray size.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to append.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let mut ua = UintArray::new::<u8>();
+    ///
+    /// for i in 1..4 {
+    ///     ua.push(i);
+    /// }
+    ///
+    /// assert_eq!(vec![1, 2, 3], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn push(&mut self, item: u128) {
+        *self = self.append(item);
+    }
+
+    /// Removes the last item of the UintArray in place and returns it.
+    /// Returns None if the UintArray is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let mut ua = UintArray::new::<u8>().extend(1..3);
+    ///
+    /// assert_eq!(Some(2), ua.pop_back_mut());
+    /// assert_eq!(Some(1), ua.pop_back_mut());
+    /// assert_eq!(None, ua.pop_back_mut());
+    /// ```
+    pub fn pop_back_mut(&mut self) -> Option<u128> {
+        let len = self.len();
+        let size = self.size();
+
+        if len == 0 {
+            return None;
+        }
+
+        let offset = (len - 1) * size + META_BITS;
+        let item = self._at(size, offset);
+
+        // No items come after the last one, so it can just be cleared.
+        self.0 = self._set_len(len - 1) & !(Self::_mask(size) << offset);
+        item
+    }
+
+    /// Returns the index of the first occurrence of an item in the UintArray.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The item to return the index of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(1..4);
+    ///
+    /// assert_eq!(Some(1), ua.index(2));
+    /// ```
+    pub fn index(&self, item: u128) -> Option<u128> {
+        let len = self.len();
+        let size = self.size();
+        self._index(item, len, size)
+    }
+
+    /// Returns the index of the last occurrence of an item in the UintArray.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The item to return the index of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(vec![1, 2, 1, 3]);
+    ///
+    /// assert_eq!(Some(2), ua.rindex(1));
+    /// ```
+    pub fn rindex(&self, item: u128) -> Option<u128> {
+        let size = self.size();
+
+        // Scan from the end so the first match is the last occurrence.
+        (0..self.len())
+            .rev()
+            .find(|&i| self._at(size, i * size + META_BITS) == Some(item))
+    }
+
+    /// Returns the indices of all occurrences of an item in the UintArray.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The item to return the indices of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(vec![1, 2, 1, 1]);
+    ///
+    /// assert_eq!(vec![0, 2, 3], ua.indices_of(1));
+    /// ```
+    pub fn indices_of(&self, item: u128) -> Vec<u128> {
+        let size = self.size();
+
+        (0..self.len())
+            .filter(|&i| self._at(size, i * size + META_BITS) == Some(item))
+            .collect()
+    }
+
+    /// Returns the index where the items of `needle` first occur in sequence in the UintArray.
+    /// An empty needle is found at index 0.
+    /// Panics if the sizes differ.
+    ///
+    /// # Arguments
+    ///
+    /// * `needle` - The UintArray whose items to search for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>().extend(1..5);
+    /// let needle = UintArray::new::<u8>().extend(2..4);
+    ///
+    /// assert_eq!(Some(1), ua.contains_subslice(&needle));
+    /// ```
+    pub fn contains_subslice(&self, needle: &UintArray) -> Option<u128> {
+        self._check_same_size_panic("contains_subslice", "needle", needle);
+
+        let len = self.len();
+        let size = self.size();
+        let needle_len = needle.len();
+
+        if needle_len > len {
+            return None;
+        }
+
+        // Compare all the items of the needle at once.
+        let mask = Self::_mask(needle_len * size);
+        let items = needle.0 >> META_BITS & mask;
+
+        (0..=len - needle_len).find(|&i| self.0 >> (i * size + META_BITS) & mask == items)
+    }
+
+    /// Returns the index of the first occurrence of an item in the UintArray.
+    fn _index(&self, item: u128, len: u128, size: u128) -> Option<u128> {
+        let mut pos = 0;
+        self._until(len, size, |x| {
+            pos += 1;
+            // Search until x == item
+            (pos - 1, x == item)
+        })
+    }
+
+    /// Run a function to all items in the UintArray until it signals to stop
+    /// and return the final value.
+    fn _until<F>(&self, len: u128, size: u128, mut f: F) -> Option<u128>
+    where
+        F: FnMut(u128) -> (u128, bool),
+    {
+        let mask = Self::_mask(size);
+
+        for i in 0..len {
+            let offset = i * size + META_BITS;
+
+            // Apply f to current item
+            let (value, stop) = f((self.0 & mask << offset) >> offset);
+
+            if stop {
+                return Some(value);
+            }
+        }
+
+        None
+    }
+
+    /// Returns the number of occurrences of an item in the UintArray.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The item to return count of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .append(1)
+    ///     .append(2)
+    ///     .append(1)
+    ///     .append(1);
+    ///
+    /// assert_eq!(3, ua.count(1));
+    /// ```
+    pub fn count(&self, item: u128) -> u128 {
+        self.aggregate(|x| if x == item { 1 } else { 0 })
+    }
+
+    /// Returns the number of occurrences of every item in the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(vec![1, 1, 2]);
+    /// let histogram = ua.histogram();
+    ///
+    /// assert_eq!(Some(&2), histogram.get(&1));
+    /// assert_eq!(Some(&1), histogram.get(&2));
+    /// ```
+    pub fn histogram(&self) -> HashMap<u128, u128> {
+        let mut counts = HashMap::new();
+        self._apply(self.len(), self.size(), |x| {
+            *counts.entry(x).or_insert(0) += 1
+        });
+        counts
+    }
+
+    /// Returns the number of unique items in the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(vec![1, 2, 1, 3, 3]);
+    ///
+    /// assert_eq!(3, ua.distinct_count());
+    /// ```
+    pub fn distinct_count(&self) -> u128 {
         let len = self.len();
         let size = self.size();
-        let cap = self.cap();
 
-        let mut iter_len: u128 = 0;
-        let mut max: u128 = 0;
-        let mut items: u128 = 0;
+        // Every possible value has a bit in a u128 presence mask.
+        if size <= 4 {
+            let mut seen: u128 = 0;
+            self._apply(len, size, |x| seen |= 1 << x);
+            return seen.count_ones() as u128;
+        }
+
+        // Otherwise count the items that don't occur earlier in the UintArray.
+        let mut pos = 0;
+        let mut n = 0;
+        self._apply(len, size, |x| {
+            if self._index(x, pos, size).is_none() {
+                n += 1;
+            }
+            pos += 1;
+        });
+        n
+    }
+
+    /// Returns the most frequent item in the UintArray, preferring the lowest item on ties.
+    /// Returns None if the UintArray is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(vec![1, 2, 2, 3, 2]);
+    ///
+    /// assert_eq!(Some(2), ua.mode());
+    /// ```
+    pub fn mode(&self) -> Option<u128> {
+        let mut counts = BTreeMap::new();
+        self._apply(self.len(), self.size(), |x| {
+            *counts.entry(x).or_insert(0) += 1
+        });
+
+        // Iterating in ascending order with a strict comparison keeps the lowest item on ties.
+        let mut mode = None;
+        let mut max = 0;
+        for (item, count) in counts {
+            if count > max {
+                max = count;
+                mode = Some(item);
+            }
+        }
+
+        mode
+    }
+
+    /// Returns a bitmask with bit `v` set for every item `v` in the UintArray.
+    /// Items that are 128 or greater have no bit in the mask and are ignored, which can only
+    /// happen with a size of 8 or more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua.extend(vec![1, 3, 5]);
+    ///
+    /// assert_eq!(0b101010, ua.presence_mask());
+    /// assert_eq!(0b10, UintArray::new::<u8>().extend(vec![1, 200]).presence_mask());
+    /// ```
+    pub fn presence_mask(&self) -> u128 {
+        let bits = Self::backing_bits();
+        let mut present: u128 = 0;
+
+        self._apply(self.len(), self.size(), |x| {
+            if x < bits {
+                present |= 1 << x;
+            }
+        });
+
+        present
+    }
+
+    /// Returns the smallest item that is not in the UintArray.
+    /// Returns None if that item doesn't fit in the UintArray size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(vec![0, 1, 3]);
+    ///
+    /// assert_eq!(Some(2), ua.find_value_gap());
+    /// ```
+    pub fn find_value_gap(&self) -> Option<u128> {
+        // The gap can never be greater than the length, which is always less than 128, so the
+        // items that presence_mask ignores don't matter.
+        let gap = self.presence_mask().trailing_ones() as u128;
+
+        if Self::_mask(self.size()) & gap != gap {
+            None
+        } else {
+            Some(gap)
+        }
+    }
+
+    /// Returns the number of consecutive zero items at the start of the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(vec![0, 0, 1, 2]);
+    ///
+    /// assert_eq!(2, ua.leading_zeros_count());
+    /// ```
+    pub fn leading_zeros_count(&self) -> u128 {
+        let size = self.size();
+
+        (0..self.len())
+            .take_while(|&i| self._at(size, i * size + META_BITS) == Some(0))
+            .count() as u128
+    }
 
-        for i in iter {
-            iter_len += 1;
+    /// Returns the number of consecutive zero items at the end of the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(vec![1, 0, 0]);
+    ///
+    /// assert_eq!(2, ua.trailing_zeros_count());
+    /// ```
+    pub fn trailing_zeros_count(&self) -> u128 {
+        let size = self.size();
 
-            if i > max {
-                max = i;
+        (0..self.len())
+            .rev()
+            .take_while(|&i| self._at(size, i * size + META_BITS) == Some(0))
+            .count() as u128
+    }
+
+    /// Returns the number of occurrences of each of the targets in the UintArray,
+    /// counting all of them in a single pass.
+    ///
+    /// # Arguments
+    ///
+    /// * `targets` - The items to return counts of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(vec![1, 2, 1, 3]);
+    ///
+    /// assert_eq!(vec![2, 1, 1], ua.count_many(&[1, 2, 3]));
+    /// ```
+    pub fn count_many(&self, targets: &[u128]) -> Vec<u128> {
+        let mut counts = vec![0; targets.len()];
+
+        self._apply(self.len(), self.size(), |x| {
+            for (count, target) in counts.iter_mut().zip(targets) {
+                if x == *target {
+                    *count += 1;
+                }
             }
+        });
+
+        counts
+    }
+
+    /// Apply a fallible function to the elements of the UintArray, stopping at the first error.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A function applied to each element of the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(vec![1, 2, 3]);
+    ///
+    /// let result = ua.try_for_each(|x| if x < 2 { Ok(()) } else { Err(x) });
+    ///
+    /// assert_eq!(Err(2), result);
+    /// ```
+    pub fn try_for_each<E, F>(&self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(u128) -> Result<(), E>,
+    {
+        let mut error = None;
 
-            if iter_len > cap {
-                panic!("Cannot extend beyond capacity.");
+        self._until(self.len(), self.size(), |x| match f(x) {
+            Ok(()) => (x, false),
+            Err(e) => {
+                error = Some(e);
+                (x, true)
             }
+        });
 
-            // Everything is put in sequence in `items`.
-            items = items | i << (iter_len - 1) * size;
+        match error {
+            Some(e) => Err(e),
+            None => Ok(()),
         }
+    }
 
-        let new_len = len + iter_len;
+    /// Aggregate the elements of the UintArray into a single u128.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A function applied to each element of the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(1..4);
+    ///
+    /// // Very simple function that just returns the value itself
+    /// let sum = ua.aggregate(|x| x);
+    ///
+    /// assert_eq!(6, sum);
+    /// ```
+    pub fn aggregate<F>(&self, f: F) -> u128
+    where
+        F: Fn(u128) -> u128,
+    {
+        self._aggregate(self.len(), self.size(), f)
+    }
 
-        // We got the max, so we only need to check once.
-        Self::_check_insert_panic(size, new_len, max);
+    /// Multiplies all the elements of the UintArray together.
+    /// The product of an empty UintArray is 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(1..5);
+    ///
+    /// assert_eq!(24, ua.product());
+    /// ```
+    pub fn product(&self) -> u128 {
+        // All items together take up at most 120 bits, so the product can't overflow.
+        let mut n = 1;
+        self._apply(self.len(), self.size(), |x| n *= x);
+        n
+    }
 
-        // Add `items` to the end.
-        UintArray(self._set_len(new_len) | items << size * len + META_BITS)
+    /// Aggregate the elements of the UintArray into a single u128.
+    fn _aggregate<F>(&self, len: u128, size: u128, f: F) -> u128
+    where
+        F: Fn(u128) -> u128,
+    {
+        let mut n = 0;
+        self._apply(len, size, |x| n += f(x));
+        n
+    }
+
+    /// Apply a function to all items in the UintArray.
+    fn _apply<F>(&self, len: u128, size: u128, mut f: F)
+    where
+        F: FnMut(u128),
+    {
+        let mask = Self::_mask(size);
+
+        for i in 0..len {
+            let offset = i * size + META_BITS;
+
+            // Apply f to current item
+            f((self.0 & mask << offset) >> offset);
+        }
+    }
+
+    /// Creates a new UintArray where every element greater than `max` is replaced by `max`.
+    /// Panics if `max` doesn't fit in the UintArray size.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The largest value allowed in the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua
+    ///     .extend(vec![1, 9, 3])
+    ///     .clamp_each(5);
+    ///
+    /// assert_eq!(Some(5), ua.at(1));
+    /// ```
+    pub fn clamp_each(&self, max: u128) -> Self {
+        let size = self.size();
+
+        Self::_check_fits_panic("clamp_each", "max", max, size, self.len());
+
+        self._map(self.len(), size, |x| if x > max { max } else { x })
+    }
+
+    /// Creates a new UintArray with `n` added to every item, wrapping around within the size.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The value to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .extend(vec![14, 15])
+    ///     .wrapping_add_scalar(1);
+    ///
+    /// assert_eq!(Some(15), ua.at(0));
+    /// assert_eq!(Some(0), ua.at(1));
+    /// ```
+    pub fn wrapping_add_scalar(&self, n: u128) -> Self {
+        let size = self.size();
+        let mask = Self::_mask(size);
+        self._map(self.len(), size, |x| x.wrapping_add(n) & mask)
+    }
+
+    /// Creates a new UintArray with `n` added to every item.
+    /// Returns an error if any of the sums doesn't fit in the UintArray size.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The value to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::{UintArray, UintArrayError};
+    /// let ua = UintArray::new_size(4).extend(vec![13, 14]);
+    ///
+    /// assert_eq!(Some(15), ua.checked_add_scalar(1).unwrap().at(1));
+    /// assert_eq!(Err(UintArrayError::ItemTooLarge), ua.checked_add_scalar(2).map(|ua| ua.raw()));
+    /// ```
+    pub fn checked_add_scalar(&self, n: u128) -> Result<Self, UintArrayError> {
+        let size = self.size();
+        let len = self.len();
+        let max = self.into_iter().max().unwrap_or(0);
+
+        match max.checked_add(n) {
+            Some(sum) if len == 0 || Self::_mask(size) & sum == sum => {
+                Ok(self._map(len, size, |x| x + n))
+            }
+            _ => Err(UintArrayError::ItemTooLarge),
+        }
+    }
+
+    /// Creates a new UintArray with `n` subtracted from every item, wrapping around within the
+    /// size.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The value to subtract.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .extend(vec![0, 1])
+    ///     .wrapping_sub_scalar(1);
+    ///
+    /// assert_eq!(Some(15), ua.at(0));
+    /// assert_eq!(Some(0), ua.at(1));
+    /// ```
+    pub fn wrapping_sub_scalar(&self, n: u128) -> Self {
+        let size = self.size();
+        let mask = Self::_mask(size);
+        self._map(self.len(), size, |x| x.wrapping_sub(n) & mask)
+    }
+
+    /// Creates a new UintArray with every item multiplied by `n`, wrapping around within the
+    /// size.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The value to multiply by.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .extend(vec![3, 4])
+    ///     .wrapping_mul_scalar(5);
+    ///
+    /// assert_eq!(Some(15), ua.at(0));
+    /// assert_eq!(Some(4), ua.at(1));
+    /// ```
+    pub fn wrapping_mul_scalar(&self, n: u128) -> Self {
+        let size = self.size();
+        let mask = Self::_mask(size);
+        self._map(self.len(), size, |x| x.wrapping_mul(n) & mask)
+    }
+
+    /// Creates a new UintArray with the bits of every item rotated left by `n` within the size.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of bits to rotate by.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .extend(vec![0b0001, 0b1000])
+    ///     .rotate_bits_each(1);
+    ///
+    /// assert_eq!(Some(0b0010), ua.at(0));
+    /// assert_eq!(Some(0b0001), ua.at(1));
+    /// ```
+    pub fn rotate_bits_each(&self, n: u32) -> Self {
+        let size = self.size();
+        let mask = Self::_mask(size);
+        let n = n as u128 % size;
+        self._map(self.len(), size, |x| (x << n | x >> (size - n)) & mask)
+    }
+
+    /// Creates a new UintArray by applying a function to all items.
+    /// The function must return values that fit in `size`.
+    fn _map<F>(&self, len: u128, size: u128, mut f: F) -> Self
+    where
+        F: FnMut(u128) -> u128,
+    {
+        let mask = Self::_mask(size);
+        let mut data = self.0 & (SIZE_MASK | LEN_MASK) | self._tag_bits();
+
+        for i in 0..len {
+            let offset = i * size + META_BITS;
+            data |= f((self.0 & mask << offset) >> offset) << offset;
+        }
+
+        UintArray(data)
+    }
+
+    /// Collects the items of the UintArray into a `Vec<u8>`.
+    /// Returns an error if the size of the UintArray isn't 8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(1..4);
+    ///
+    /// assert_eq!(Ok(vec![1, 2, 3]), ua.to_u8_vec());
+    /// ```
+    pub fn to_u8_vec(&self) -> Result<Vec<u8>, UintArrayError> {
+        self._check_size(8)?;
+        Ok(self.into_iter().map(|x| x as u8).collect())
+    }
+
+    /// Collects the items of the UintArray into a `Vec<u16>`.
+    /// Returns an error if the size of the UintArray isn't 16.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u16>();
+    ///
+    /// let ua = ua.extend(1..4);
+    ///
+    /// assert_eq!(Ok(vec![1, 2, 3]), ua.to_u16_vec());
+    /// ```
+    pub fn to_u16_vec(&self) -> Result<Vec<u16>, UintArrayError> {
+        self._check_size(16)?;
+        Ok(self.into_iter().map(|x| x as u16).collect())
     }
 
-    /// Clears all values from the UintArray.
+    /// Collects the items of the UintArray into a `Vec<u32>`.
+    /// Returns an error if the size of the UintArray isn't 32.
     ///
     /// # Examples
     ///
     /// ```
     /// use uintarray::UintArray;
-    /// let ua = UintArray::new::<u8>();
+    /// let ua = UintArray::new::<u32>();
     ///
-    /// let ua = ua
-    ///     .append(15)
-    ///     .append(14)
-    ///     .clear();
+    /// let ua = ua.extend(1..3);
     ///
-    /// assert_eq!(0, ua.len());
-    /// assert_eq!(8, ua.size());
+    /// assert_eq!(Ok(vec![1, 2]), ua.to_u32_vec());
     /// ```
-    #[inline]
-    pub fn clear(&self) -> Self {
-        UintArray(self.0 & SIZE_MASK)
+    pub fn to_u32_vec(&self) -> Result<Vec<u32>, UintArrayError> {
+        self._check_size(32)?;
+        Ok(self.into_iter().map(|x| x as u32).collect())
     }
 
-    /// Removes the first occurrence of an item from the UintArray.
+    /// Collects the items of the UintArray into a `Vec<u64>`.
+    /// Returns an error if the size of the UintArray isn't 64.
     ///
-    /// # Arguments
+    /// # Examples
     ///
-    /// * `item` - The item to remove.
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u64>();
+    ///
+    /// let ua = ua.append(1);
+    ///
+    /// assert_eq!(Ok(vec![1]), ua.to_u64_vec());
+    /// ```
+    pub fn to_u64_vec(&self) -> Result<Vec<u64>, UintArrayError> {
+        self._check_size(64)?;
+        Ok(self.into_iter().map(|x| x as u64).collect())
+    }
+
+    /// Returns the little-endian bytes of every item, one after the other.
+    /// Panics if the size of the UintArray isn't a multiple of 8.
     ///
     /// # Examples
     ///
     /// ```
     /// use uintarray::UintArray;
-    /// let ua = UintArray::new::<u8>();
+    /// let ua = UintArray::new::<u16>();
     ///
-    /// let ua = ua
-    ///     .extend(1..4)
-    ///     .remove(2);
+    /// let ua = ua.extend(vec![1, 258]);
     ///
-    /// assert_eq!(Some(3), ua.at(1));
-    /// assert_eq!(2, ua.len());
+    /// assert_eq!(vec![1, 0, 2, 1], ua.to_bytes());
     /// ```
-    pub fn remove(&self, item: u128) -> Self {
-        let len = self.len();
+    pub fn to_bytes(&self) -> Vec<u8> {
         let size = self.size();
 
-        let pos = self._index(item, len, size);
-
-        let pos = match pos {
-            Some(i) => i,
-            None => return *self,
-        };
-
-        let offset = pos * size + META_BITS;
-        let pos_mask = Self::_mask(offset);
+        if !size.is_multiple_of(8) {
+            Self::_panic(
+                "to_bytes",
+                format_args!("size is not a multiple of 8"),
+                size,
+                self.len(),
+            );
+        }
 
-        // Same operation as that of self.pop()
-        UintArray(self._set_len(len - 1) & pos_mask | (self.0 & !pos_mask) >> size & !pos_mask)
+        let bytes = (size / 8) as usize;
+        self.into_iter()
+            .flat_map(|x| x.to_le_bytes()[..bytes].to_vec())
+            .collect()
     }
 
-    /// Removes an item from the UintArray at a given index and returns it and the UintArray.
+    /// Returns the uint backing the UintArray as a `u64`, for storing small UintArrays in less
+    /// space. Any value stored with `set_spare` is dropped.
+    /// Returns an error if the length, size and items use more than 64 bits.
     ///
-    /// # Arguments
+    /// # Examples
     ///
-    /// * `pos` - The index of the item to remove and return.
+    /// ```
+    /// use uintarray::{UintArray, UintArrayError};
+    /// let ua = UintArray::new::<u8>().extend(1..4);
+    ///
+    /// assert_eq!(Ok(ua.raw() as u64), ua.to_u64());
+    /// assert_eq!(Err(UintArrayError::DoesNotFit), ua.extend(4..12).to_u64());
+    /// ```
+    pub fn to_u64(&self) -> Result<u64, UintArrayError> {
+        if self.bits_used() > 64 {
+            return Err(UintArrayError::DoesNotFit);
+        }
+
+        Ok(self.normalize().0 as u64)
+    }
+
+    /// Collects the items of the UintArray into an array of length `N`.
+    /// Returns an error if the length of the UintArray isn't `N`.
     ///
     /// # Examples
     ///
@@ -448,43 +3445,34 @@ impl UintArray {
     /// use uintarray::UintArray;
     /// let ua = UintArray::new::<u8>();
     ///
-    /// let ua = ua
-    ///     .extend(1..4);
-    ///
-    /// let (ua, item) = ua.pop(1);
+    /// let ua = ua.extend(1..4);
     ///
-    /// assert_eq!(Some(2), item);
+    /// assert_eq!(Ok([1, 2, 3]), ua.try_to_array::<3>());
     /// ```
-    pub fn pop(&self, pos: u128) -> (Self, Option<u128>) {
-        let len = self.len();
-        let size = self.size();
+    pub fn try_to_array<const N: usize>(&self) -> Result<[u128; N], UintArrayError> {
+        if self.len() != N as u128 {
+            return Err(UintArrayError::LengthMismatch);
+        }
 
-        if pos >= len {
-            return (*self, None);
+        let mut items = [0; N];
+        for (slot, item) in items.iter_mut().zip(*self) {
+            *slot = item;
         }
 
-        let offset = pos * size + META_BITS;
-        let pos_mask = Self::_mask(offset);
+        Ok(items)
+    }
 
-        (
-            // Move everything after `pos` down by `size`, discarding any overlap and effectivly
-            // removing the item in question.
-            //
-            // If the offset is at the transition from 0's to 1's and the size is 2,
-            // it will do the following:
-            //
-            // 1111110000 ->     0000 -> 11110000
-            //               111111
-            UintArray(self._set_len(len - 1) & pos_mask | (self.0 & !pos_mask) >> size & !pos_mask),
-            self._at(size, offset),
-        )
+    /// Returns an error if the size of the UintArray isn't `size`.
+    #[inline]
+    pub(crate) fn _check_size(&self, size: u128) -> Result<(), UintArrayError> {
+        if self.size() != size {
+            return Err(UintArrayError::SizeMismatch);
+        }
+
+        Ok(())
     }
 
-    /// Returns the index of the first occurrence of an item in the UintArray.
-    ///
-    /// # Arguments
-    ///
-    /// * `item` - The item to return the index of.
+    /// Creates a new UintArray with only the first occurrence of every item.
     ///
     /// # Examples
     ///
@@ -493,53 +3481,65 @@ impl UintArray {
     /// let ua = UintArray::new::<u8>();
     ///
     /// let ua = ua
-    ///     .extend(1..4);
+    ///     .extend(vec![1, 2, 1, 3, 2])
+    ///     .to_set();
     ///
-    /// assert_eq!(Some(1), ua.index(2));
+    /// assert_eq!(vec![1, 2, 3], ua.into_iter().collect::<Vec<u128>>());
     /// ```
-    pub fn index(&self, item: u128) -> Option<u128> {
-        let len = self.len();
+    pub fn to_set(&self) -> Self {
         let size = self.size();
-        self._index(item, len, size)
-    }
+        let mut set = self.clear();
 
-    /// Returns the index of the first occurrence of an item in the UintArray.
-    fn _index(&self, item: u128, len: u128, size: u128) -> Option<u128> {
-        let mut pos = 0;
-        self._until(len, size, |x| {
-            pos += 1;
-            // Search until x == item
-            (pos - 1, x == item)
-        })
+        self._apply(self.len(), size, |x| {
+            let len = set.len();
+            if set._index(x, len, size).is_none() {
+                set = set._append("to_set", x, size, len);
+            }
+        });
+
+        set
     }
 
-    /// Run a function to all items in the UintArray until it signals to stop
-    /// and return the final value.
-    fn _until<F>(&self, len: u128, size: u128, mut f: F) -> Option<u128>
+    /// Splits the UintArray into the items that satisfy a predicate and those that don't,
+    /// keeping their order.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The predicate applied to each element of the UintArray.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(1..5);
+    /// let (even, odd) = ua.partition(|x| x % 2 == 0);
+    ///
+    /// assert_eq!(vec![2, 4], even.into_iter().collect::<Vec<u128>>());
+    /// assert_eq!(vec![1, 3], odd.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn partition<F>(&self, f: F) -> (Self, Self)
     where
-        F: FnMut(u128) -> (u128, bool),
+        F: Fn(u128) -> bool,
     {
-        let mask = Self::_mask(size);
-
-        for i in 0..len {
-            let offset = i * size + META_BITS;
-
-            // Apply f to current item
-            let (value, stop) = f((self.0 & mask << offset) >> offset);
+        let size = self.size();
+        let mut matching = self.clear();
+        let mut rest = self.clear();
 
-            if stop {
-                return Some(value);
-            }
-        }
+        self._apply(self.len(), size, |x| {
+            let ua = if f(x) { &mut matching } else { &mut rest };
+            *ua = ua._append("partition", x, size, ua.len());
+        });
 
-        None
+        (matching, rest)
     }
 
-    /// Returns the number of occurrences of an item in the UintArray.
+    /// Creates a new UintArray with only the items that occur at least `min_count` times.
     ///
     /// # Arguments
     ///
-    /// * `item` - The item to return count of.
+    /// * `min_count` - How many times an item must occur to be kept.
     ///
     /// # Examples
     ///
@@ -548,22 +3548,21 @@ impl UintArray {
     /// let ua = UintArray::new::<u8>();
     ///
     /// let ua = ua
-    ///     .append(1)
-    ///     .append(2)
-    ///     .append(1)
-    ///     .append(1);
+    ///     .extend(vec![1, 2, 2, 3, 2])
+    ///     .filter_frequent(2);
     ///
-    /// assert_eq!(3, ua.count(1));
+    /// assert_eq!(vec![2, 2, 2], ua.into_iter().collect::<Vec<u128>>());
     /// ```
-    pub fn count(&self, item: u128) -> u128 {
-        self.aggregate(|x| if x == item { 1 } else { 0 })
+    pub fn filter_frequent(&self, min_count: u128) -> Self {
+        let counts = self.histogram();
+        let (frequent, _) = self.partition(|x| counts[&x] >= min_count);
+        frequent
     }
 
-    /// Aggregate the elements of the UintArray into a single u128.
+    /// Computes a checksum of the UintArray for detecting corruption.
     ///
-    /// # Arguments
-    ///
-    /// * `f` - A function applied to each element of the UintArray.
+    /// The checksum is the XOR of the 16 little-endian bytes of the uint, after clearing the
+    /// spare bits above the last item so that only the length, size and items contribute.
     ///
     /// # Examples
     ///
@@ -571,43 +3570,61 @@ impl UintArray {
     /// use uintarray::UintArray;
     /// let ua = UintArray::new::<u8>();
     ///
-    /// let ua = ua.extend(1..4);
-    ///
-    /// // Very simple function that just returns the value itself
-    /// let sum = ua.aggregate(|x| x);
+    /// let ua = ua.extend(vec![1, 2]);
     ///
-    /// assert_eq!(6, sum);
+    /// // The bytes are 0b00010011, 1 and 2
+    /// assert_eq!(0b00010011 ^ 1 ^ 2, ua.checksum());
     /// ```
-    pub fn aggregate<F>(&self, f: F) -> u128
-    where
-        F: Fn(u128) -> u128,
-    {
-        self._aggregate(self.len(), self.size(), f)
+    pub fn checksum(&self) -> u8 {
+        self.normalize()
+            .0
+            .to_le_bytes()
+            .iter()
+            .fold(0, |checksum, byte| checksum ^ byte)
     }
 
-    /// Aggregate the elements of the UintArray into a single u128.
-    fn _aggregate<F>(&self, len: u128, size: u128, f: F) -> u128
-    where
-        F: Fn(u128) -> u128,
-    {
-        let mut n = 0;
-        self._apply(len, size, |x| n += f(x));
-        n
+    /// Run-length encodes the UintArray into pairs of an item and how many times it's repeated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(vec![1, 1, 2, 3, 3, 3]);
+    ///
+    /// assert_eq!(vec![(1, 2), (2, 1), (3, 3)], ua.rle());
+    /// ```
+    pub fn rle(&self) -> Vec<(u128, u128)> {
+        self.group_runs().collect()
     }
 
-    /// Apply a function to all items in the UintArray.
-    fn _apply<F>(&self, len: u128, size: u128, mut f: F)
-    where
-        F: FnMut(u128),
-    {
-        let mask = Self::_mask(size);
+    /// Iterates runs of equal consecutive items as `(item, count)` pairs, the lazy form of
+    /// `rle`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(vec![1, 1, 2]);
+    ///
+    /// assert_eq!(vec![(1, 2), (2, 1)], ua.group_runs().collect::<Vec<(u128, u128)>>());
+    /// ```
+    pub fn group_runs(&self) -> impl Iterator<Item = (u128, u128)> {
+        let mut items = self.into_iter().peekable();
 
-        for i in 0..len {
-            let offset = i * size + META_BITS;
+        std::iter::from_fn(move || {
+            let item = items.next()?;
+            let mut count = 1;
 
-            // Apply f to current item
-            f((self.0 & mask << offset) >> offset);
-        }
+            while items.next_if_eq(&item).is_some() {
+                count += 1;
+            }
+
+            Some((item, count))
+        })
     }
 
     /// Returns a prettily formatted representation of the UintArray.
@@ -627,4 +3644,136 @@ impl UintArray {
 
         formatted
     }
+
+    /// Parses the bits of a UintArray, most significant bit first, as returned by `format`.
+    /// Whitespace is ignored.
+    /// Returns an error if there aren't exactly 128 bits, or if the encoded size or length is
+    /// invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - The bits to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>().extend(1..4);
+    ///
+    /// let parsed = UintArray::from_bits(&ua.format()).unwrap();
+    ///
+    /// assert_eq!(ua.raw(), parsed.raw());
+    /// ```
+    pub fn from_bits(bits: &str) -> Result<Self, UintArrayError> {
+        let total = size_of::<u128>() as u128 * 8;
+        let mut data: u128 = 0;
+        let mut count: u128 = 0;
+
+        for c in bits.chars().filter(|c| !c.is_whitespace()) {
+            let bit = match c {
+                '0' => 0,
+                '1' => 1,
+                _ => return Err(UintArrayError::InvalidFormat),
+            };
+
+            count += 1;
+
+            if count > total {
+                return Err(UintArrayError::InvalidFormat);
+            }
+
+            data = data << 1 | bit;
+        }
+
+        if count != total {
+            return Err(UintArrayError::InvalidFormat);
+        }
+
+        Self::_check_raw(data)
+    }
+
+    /// Returns the UintArray backed by `data`, or an error if its size or length is invalid.
+    fn _check_raw(data: u128) -> Result<Self, UintArrayError> {
+        let ua = UintArray(data);
+
+        Self::_check_size_valid(ua.size() as usize)?;
+
+        if ua.len() > ua.cap() {
+            return Err(UintArrayError::LengthExceedsCapacity);
+        }
+
+        Ok(ua)
+    }
+
+    /// Returns the uint backing the UintArray as a JSON string, e.g. `"524314"`.
+    /// JSON numbers are usually parsed as doubles, which can't hold every u128 exactly, so the
+    /// value is deliberately quoted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new_size(4).extend(vec![0, 0, 8]);
+    ///
+    /// assert_eq!("\"524314\"", ua.to_json_number());
+    /// ```
+    pub fn to_json_number(&self) -> String {
+        format!("\"{}\"", self.0)
+    }
+
+    /// Parses a UintArray from the JSON string returned by `to_json_number`.
+    /// An unquoted number is accepted as well.
+    /// Returns an error if the string isn't a number, or if the encoded size or length is
+    /// invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON string or number to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::from_json_number("\"524314\"").unwrap();
+    ///
+    /// assert_eq!(vec![0, 0, 8], ua.into_iter().collect::<Vec<u128>>());
+    /// ```
+    pub fn from_json_number(json: &str) -> Result<Self, UintArrayError> {
+        let json = json.trim();
+        let number = json
+            .strip_prefix('"')
+            .and_then(|n| n.strip_suffix('"'))
+            .unwrap_or(json);
+
+        // `parse` accepts a leading '+', which isn't valid JSON.
+        if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(UintArrayError::InvalidFormat);
+        }
+
+        let data = number
+            .parse::<u128>()
+            .map_err(|_| UintArrayError::InvalidFormat)?;
+
+        Self::_check_raw(data)
+    }
+
+    /// Returns the items of the UintArray with their positions, e.g. `[0]=1 [1]=2 [2]=3`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::UintArray;
+    /// let ua = UintArray::new::<u8>();
+    ///
+    /// let ua = ua.extend(1..4);
+    ///
+    /// assert_eq!("[0]=1 [1]=2 [2]=3", ua.format_indexed());
+    /// ```
+    pub fn format_indexed(&self) -> String {
+        self.into_iter()
+            .enumerate()
+            .map(|(i, item)| format!("[{}]={}", i, item))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
 }
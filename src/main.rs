@@ -17,11 +17,11 @@ fn main() {
     // Convert the msg to a ua
     let ua = encode(msg);
 
-    // ua.0 holds the actual u128 that stores the data
-    println!("Encoded: {}", ua.0);
+    // ua.raw() is the actual u128 that stores the data
+    println!("Encoded: {}", ua.raw());
 
     // Collect the chars in the u128 (pretend we only had the u128 and not also a UintArray)
-    let decoded_msg = decode(ua.0);
+    let decoded_msg = decode(ua.raw());
     println!("Decoded: {}", decoded_msg);
 }
 
@@ -30,7 +30,7 @@ fn encode(msg: &str) -> UintArray {
     let ua = UintArray::new::<u8>();
 
     // Add the chars to ua
-    let ua = ua.extend(msg.as_bytes().into_iter().map(|c| *c as u128));
+    let ua = ua.extend(msg.as_bytes().iter().map(|c| *c as u128));
 
     ua
 }
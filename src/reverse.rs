@@ -0,0 +1,164 @@
+//! A UintArray that packs its items from the high bits downwards.
+
+use crate::UintArray;
+use std::iter::IntoIterator;
+use std::mem::size_of;
+
+// Number of bits in the uint backing a ReverseUintArray.
+const BITS: u128 = size_of::<u128>() as u128 * 8;
+
+/// Multiple values stored in a single uint, with the first item in the highest bits.
+///
+/// Uses the same size and length encoding as a `UintArray`, but the item at position `pos`
+/// ends at bit `128 - pos * size` instead of starting at bit `8 + pos * size`.
+#[derive(Copy, Clone)]
+pub struct ReverseUintArray(UintArray);
+
+/// Iteration over a ReverseUintArray.
+pub struct ReverseUintArrayIterator {
+    ua: ReverseUintArray,
+    index: u128,
+}
+
+impl IntoIterator for ReverseUintArray {
+    type Item = u128;
+    type IntoIter = ReverseUintArrayIterator;
+
+    fn into_iter(self) -> Self::IntoIter {
+        ReverseUintArrayIterator { ua: self, index: 0 }
+    }
+}
+
+impl Iterator for ReverseUintArrayIterator {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        // Only advance within bounds, so a start past the end can't overflow.
+        let item = self.ua.at(self.index)?;
+        self.index += 1;
+        Some(item)
+    }
+}
+
+impl ReverseUintArray {
+    /// Creates a new ReverseUintArray with a specific data type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::ReverseUintArray;
+    /// let ua = ReverseUintArray::new::<u8>();
+    ///
+    /// assert_eq!(8, ua.size());
+    /// ```
+    pub fn new<T>() -> Self {
+        ReverseUintArray(UintArray::new::<T>())
+    }
+
+    /// Creates a new ReverseUintArray with a specific data size.
+    /// Size cannot be more than half of the ReverseUintArray data type size and must be a
+    /// power of 2.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size in bits of the contained data.
+    pub fn new_size(size: usize) -> Self {
+        ReverseUintArray(UintArray::new_size(size))
+    }
+
+    /// Gets the uint backing the ReverseUintArray.
+    #[inline]
+    pub fn raw(&self) -> u128 {
+        self.0.raw()
+    }
+
+    /// Gets the bit size of values stored in the ReverseUintArray.
+    #[inline]
+    pub fn size(&self) -> u128 {
+        self.0.size()
+    }
+
+    /// Gets the current length of the ReverseUintArray.
+    #[inline]
+    pub fn len(&self) -> u128 {
+        self.0.len()
+    }
+
+    /// Whether the ReverseUintArray contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// How many elements can be stored in the ReverseUintArray - its capacity.
+    #[inline]
+    pub fn cap(&self) -> u128 {
+        self.0.cap()
+    }
+
+    /// Get the item at position `pos`. First item is at `pos = 0` (i.e. it's zero-indexed).
+    /// Returns None if out of bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - Position of the item to get.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::ReverseUintArray;
+    /// let ua = ReverseUintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .append(2)
+    ///     .append(4);
+    ///
+    /// assert_eq!(Some(4), ua.at(1));
+    /// assert_eq!(0x24, ua.raw() >> 120);
+    /// ```
+    pub fn at(&self, pos: u128) -> Option<u128> {
+        if pos >= self.len() {
+            None
+        } else {
+            let size = self.size();
+            self.0._at(size, Self::_offset(size, pos))
+        }
+    }
+
+    /// Gets the offset of the item at position `pos`.
+    #[inline]
+    fn _offset(size: u128, pos: u128) -> u128 {
+        BITS - (pos + 1) * size
+    }
+
+    /// Creates a new ReverseUintArray with the given item appended to the end.
+    /// Panics if appending would exceed capacity or if the item doesn't fit in the size.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - Item to append.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uintarray::ReverseUintArray;
+    /// let ua = ReverseUintArray::new_size(4);
+    ///
+    /// let ua = ua
+    ///     .append(1)
+    ///     .append(2);
+    ///
+    /// assert_eq!(Some(1), ua.at(0));
+    /// assert_eq!(2, ua.len());
+    /// ```
+    pub fn append(&self, item: u128) -> Self {
+        let len = self.len();
+        let size = self.size();
+
+        UintArray::_check_insert_panic("append", size, len, 1, item);
+
+        ReverseUintArray(UintArray(
+            self.0._set_len(len + 1) | item << Self::_offset(size, len),
+        ))
+    }
+}
@@ -1,4 +1,7 @@
-use uintarray::UintArray;
+use std::convert::TryFrom;
+use uintarray::{
+    FixedUintArray, ReverseUintArray, UintArray, UintArrayError, MAX_ELEMENT_SIZE, TAG_BITS,
+};
 
 #[cfg(test)]
 mod tests {
@@ -8,14 +11,14 @@ mod tests {
     fn test_new() {
         let ua = UintArray::new::<char>();
 
-        assert_eq!(5, ua.0);
+        assert_eq!(5, ua.raw());
     }
 
     #[test]
     fn test_new_size() {
         let ua = UintArray::new_size(4);
 
-        assert_eq!(2, ua.0);
+        assert_eq!(2, ua.raw());
     }
 
     #[test]
@@ -27,56 +30,719 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_from_len_exceeds_cap() {
-        UintArray::from(69421);
+        let _ = UintArray::from(69421);
+    }
+
+    #[test]
+    fn test_into_u128() {
+        let ua = UintArray::from(524_314);
+        let raw: u128 = ua.into();
+        assert_eq!(524_314, raw);
+        assert_eq!(&524_314, ua.as_ref());
+    }
+
+    #[test]
+    fn test_from_raw() {
+        let ua = unsafe { UintArray::from_raw(524_314) };
+        assert_eq!(UintArray::from(524_314).raw(), ua.raw());
+        assert_eq!(Some(8), ua.at(2));
+
+        // Not validated, unlike from.
+        let ua = unsafe { UintArray::from_raw(69421) };
+        assert!(ua.len() > ua.cap());
+    }
+
+    #[test]
+    fn test_repair() {
+        // Size 16 with length 31 and every data bit set.
+        let ua = unsafe { UintArray::from_raw(u128::MAX << 8 | 31 << 3 | 4) };
+        let repaired = ua.repair();
+        assert_eq!(7, repaired.len());
+        assert_eq!(16, repaired.size());
+        assert_eq!(0, repaired.spare());
+        assert_eq!(vec![0xFFFF; 7], repaired.into_iter().collect::<Vec<u128>>());
+        assert_eq!(repaired.raw(), UintArray::from(repaired.raw()).raw());
+    }
+
+    #[test]
+    fn test_repair_valid() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(ua.raw(), ua.repair().raw());
+        assert_eq!(ua.raw(), ua.set_spare(5).repair().raw());
+
+        let ua = UintArray::new::<u8>().extend(0..15);
+        assert_eq!(ua.raw(), ua.repair().raw());
+    }
+
+    #[test]
+    fn test_normalize() {
+        let ua = UintArray::from(524_314);
+        let a = ua.set_spare(1);
+        let b = ua.set_spare(0xFFFF);
+        assert_ne!(a.raw(), b.raw());
+        assert_eq!(a.normalize().raw(), b.normalize().raw());
+        assert_eq!(ua.raw(), a.normalize().raw());
+
+        let full = UintArray::new::<u8>().extend(0..15);
+        assert_eq!(full.raw(), full.normalize().raw());
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(UintArray::from(524_314).is_valid());
+        assert!(UintArray::new::<u8>().is_valid());
+        assert!(UintArray::new::<u8>().extend(0..15).is_valid());
+        assert!(UintArray::new::<u64>().append(u64::MAX as u128).is_valid());
+
+        // Length 31 exceeds the capacity of 7 for size 16.
+        let ua = unsafe { UintArray::from_raw(31 << 3 | 4) };
+        assert!(!ua.is_valid());
+        assert!(ua.repair().is_valid());
+
+        // Stray bits after the last item.
+        let ua = unsafe { UintArray::from_raw(1 << 100 | 524_314) };
+        assert!(!ua.is_valid());
+        assert!(ua.normalize().is_valid());
+
+        // Size 128 is larger than the largest element size.
+        assert!(!unsafe { UintArray::from_raw(7) }.is_valid());
+    }
+
+    #[test]
+    fn test_into_parts() {
+        assert_eq!((4, 3, vec![0, 0, 8]), UintArray::from(524_314).into_parts());
+        assert_eq!((16, 0, vec![]), UintArray::new::<u16>().into_parts());
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let ua = UintArray::from_parts(4, &[0, 0, 8]).unwrap();
+        assert_eq!(524_314, ua.raw());
+
+        let (size, _, items) = ua.into_parts();
+        let round_trip = UintArray::from_parts(size as usize, &items).unwrap();
+        assert_eq!(ua.raw(), round_trip.raw());
+
+        let ua = UintArray::from_parts(8, &(0..15).collect::<Vec<u128>>()).unwrap();
+        assert_eq!(15, ua.len());
+        assert_eq!(0, UintArray::from_parts(64, &[]).unwrap().len());
+    }
+
+    #[test]
+    fn test_from_parts_invalid() {
+        assert_eq!(
+            Err(UintArrayError::InvalidSize),
+            UintArray::from_parts(128, &[]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::NotPowerOfTwo),
+            UintArray::from_parts(12, &[]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::NotPowerOfTwo),
+            UintArray::from_parts(0, &[]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::ItemTooLarge),
+            UintArray::from_parts(4, &[1, 16]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            UintArray::from_parts(64, &[1, 2]).map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_from_parts_max_len() {
+        // The length can't exceed 31, even though size 1 has a capacity of 120.
+        let ua = UintArray::from_parts(1, &[1; 31]).unwrap();
+        assert_eq!(31, ua.len());
+        assert!(ua.is_valid());
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            UintArray::from_parts(1, &[1; 32]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            UintArray::from_parts(1, &[1; 40]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            UintArray::from_parts(2, &[1; 40]).map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_auto() {
+        let ua = UintArray::auto(&[1, 2, 255]);
+        assert_eq!(8, ua.size());
+        assert_eq!(
+            Ok(ua.raw()),
+            UintArray::from_parts(8, &[1, 2, 255]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Ok(ua.raw()),
+            ua.to_string().parse::<UintArray>().map(|ua| ua.raw())
+        );
+
+        assert_eq!(4, UintArray::auto(&[0, 0, 8]).size());
+        assert_eq!(524_314, UintArray::auto(&[0, 0, 8]).raw());
+        assert_eq!(64, UintArray::auto(&[1 << 40]).size());
+        assert_eq!(0, UintArray::auto(&[]).len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_auto_exceed_capacity() {
+        UintArray::auto(&[1 << 40, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "auto: capacity exceeded (len=0, cap=31, size=1)")]
+    fn test_auto_exceed_max_len() {
+        UintArray::auto(&[1; 40]);
+    }
+
+    #[test]
+    fn test_auto_max_len() {
+        let ua = UintArray::auto(&[1; 31]);
+        assert_eq!(1, ua.size());
+        assert_eq!(31, ua.len());
+        assert!(ua.is_valid());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_auto_does_not_fit() {
+        UintArray::auto(&[1 << 64]);
+    }
+
+    #[test]
+    fn test_chunk_into_arrays() {
+        let items = (0..40).map(|i| i % 16).collect::<Vec<u128>>();
+        let arrays = UintArray::chunk_into_arrays(4, &items);
+        assert_eq!(2, arrays.len());
+        assert_eq!(30, arrays[0].len());
+        assert_eq!(10, arrays[1].len());
+        assert_eq!(
+            items,
+            arrays
+                .iter()
+                .flat_map(|ua| ua.into_iter())
+                .collect::<Vec<u128>>()
+        );
+
+        assert_eq!(1, UintArray::chunk_into_arrays(8, &[1; 15]).len());
+        assert!(UintArray::chunk_into_arrays(8, &[]).is_empty());
+
+        // Size 1 is limited by the largest length rather than its capacity.
+        let arrays = UintArray::chunk_into_arrays(1, &[1; 40]);
+        assert_eq!(
+            vec![31, 9],
+            arrays.iter().map(|ua| ua.len()).collect::<Vec<u128>>()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chunk_into_arrays_does_not_fit() {
+        UintArray::chunk_into_arrays(4, &[1, 16]);
     }
 
     #[test]
     fn test_size() {
-        let ua = UintArray(69420);
+        let ua = UintArray::from(69420);
         assert_eq!(16, ua.size());
     }
 
     #[test]
     fn test_cap() {
-        let ua = UintArray(69420);
+        let ua = UintArray::from(69420);
         assert_eq!(7, ua.cap());
     }
 
+    #[test]
+    fn test_cap_all_sizes() {
+        let sizes = [1, 2, 4, 8, 16, 32, 64];
+        let caps = [120, 60, 30, 15, 7, 3, 1];
+
+        for (size, cap) in sizes.iter().zip(caps.iter()) {
+            assert_eq!(*cap, UintArray::new_size(*size).cap(), "size={}", size);
+        }
+    }
+
+    #[test]
+    fn test_wasted_bits() {
+        let sizes = [1, 2, 4, 8, 16, 32, 64];
+        let wasted = [89, 58, 0, 0, 8, 24, 56];
+
+        for (size, wasted) in sizes.iter().zip(wasted.iter()) {
+            assert_eq!(
+                *wasted,
+                UintArray::new_size(*size).wasted_bits(),
+                "size={}",
+                size
+            );
+        }
+    }
+
+    #[test]
+    fn test_size_for_max() {
+        assert_eq!(1, UintArray::size_for_max(0));
+        assert_eq!(1, UintArray::size_for_max(1));
+        assert_eq!(2, UintArray::size_for_max(2));
+        assert_eq!(4, UintArray::size_for_max(15));
+        assert_eq!(8, UintArray::size_for_max(16));
+        assert_eq!(8, UintArray::size_for_max(255));
+        assert_eq!(16, UintArray::size_for_max(256));
+        assert_eq!(64, UintArray::size_for_max(u64::MAX as u128));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "size_for_max: max=18446744073709551616 does not fit (len=0, cap=1, size=64)"
+    )]
+    fn test_size_for_max_too_large() {
+        UintArray::size_for_max(u64::MAX as u128 + 1);
+    }
+
+    #[test]
+    fn test_max_value() {
+        assert_eq!(15, UintArray::new_size(4).max_value());
+        assert_eq!(1, UintArray::new_size(1).max_value());
+        assert_eq!(u8::MAX as u128, UintArray::new::<u8>().max_value());
+        assert_eq!(u64::MAX as u128, UintArray::new::<u64>().max_value());
+    }
+
+    #[test]
+    fn test_capacity_of() {
+        const CAP: u128 = UintArray::capacity_of::<u8>();
+        assert_eq!(15, CAP);
+        assert_eq!(
+            UintArray::new::<u16>().cap(),
+            UintArray::capacity_of::<u16>()
+        );
+        assert_eq!(1, UintArray::capacity_of::<u64>());
+    }
+
+    #[test]
+    fn test_backing_bits() {
+        const BITS: u128 = UintArray::backing_bits();
+        assert_eq!(128, BITS);
+    }
+
     #[test]
     #[should_panic]
     fn test_size_big_panic() {
         UintArray::new_size(128);
     }
 
+    #[test]
+    fn test_max_element_size() {
+        assert_eq!(64, MAX_ELEMENT_SIZE);
+        assert_eq!(64, UintArray::new_size(MAX_ELEMENT_SIZE).size());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_max_element_size_exceeded() {
+        UintArray::new_size(MAX_ELEMENT_SIZE * 2);
+    }
+
     #[test]
     #[should_panic]
     fn test_size_power_of_two_panic() {
         UintArray::new_size(15);
     }
 
+    #[test]
+    fn test_bits_used() {
+        assert_eq!(20, UintArray::new_size(4).extend(1..4).bits_used());
+        assert_eq!(8, UintArray::new::<u8>().bits_used());
+        assert_eq!(128, UintArray::new::<u8>().extend(0..15).bits_used());
+    }
+
+    #[test]
+    fn test_spare_bits() {
+        assert_eq!(108, UintArray::from(524_314).spare_bits());
+        assert_eq!(120, UintArray::new::<u8>().spare_bits());
+        assert_eq!(56, UintArray::new::<u64>().append(1).spare_bits());
+    }
+
+    #[test]
+    fn test_set_spare() {
+        let ua = UintArray::from(524_314);
+        let stashed = ua.set_spare(0xABCD);
+        assert_eq!(0xABCD, stashed.spare());
+        assert_eq!(ua.len(), stashed.len());
+        assert_eq!(
+            ua.into_iter().collect::<Vec<u128>>(),
+            stashed.into_iter().collect::<Vec<u128>>()
+        );
+
+        // Setting again replaces the previous value.
+        assert_eq!(1, stashed.set_spare(1).spare());
+        assert_eq!(ua.raw(), stashed.set_spare(0).raw());
+
+        let full = UintArray::new::<u8>().extend(0..15);
+        assert_eq!(0, full.spare_bits());
+        assert_eq!(0, full.spare());
+        assert_eq!(full.raw(), full.set_spare(0).raw());
+    }
+
+    #[test]
+    fn test_set_spare_append() {
+        // Appending clears the value instead of mixing it into the new items.
+        let ua = UintArray::new::<u8>().extend(1..4).set_spare(0xABCD);
+        let appended = ua.append(4);
+        assert_eq!(Some(4), appended.at(3));
+        assert_eq!(0, appended.spare());
+        assert!(appended.is_valid());
+
+        let expected = vec![1, 2, 3, 4, 5];
+        assert_eq!(expected, ua.extend(4..6).into_iter().collect::<Vec<u128>>());
+        assert_eq!(Some(4), ua.append_repeat(4, 2).at(4));
+        assert_eq!(Some(4), ua.try_append(4).unwrap().at(3));
+        let other = UintArray::new::<u8>().extend(4..6);
+        assert_eq!(
+            expected,
+            ua.extend_array(&other).into_iter().collect::<Vec<u128>>()
+        );
+        let built = ua.with(|b| {
+            b.append(4);
+        });
+        assert_eq!(Some(4), built.at(3));
+        assert!(built.is_valid());
+
+        // The highest bits of the value are kept as the tag.
+        let tagged = ua.set_spare(0xAB << 88 | 0xCD).append(4);
+        assert_eq!(0xAB, tagged.tag());
+        assert_eq!(Some(4), tagged.at(3));
+        assert!(tagged.is_valid());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "set_spare: value=72057594037927936 does not fit in spare_bits=56 (len=1, cap=1, size=64)"
+    )]
+    fn test_set_spare_does_not_fit() {
+        UintArray::new::<u64>().append(1).set_spare(1 << 56);
+    }
+
+    #[test]
+    fn test_with_tag() {
+        let ua = UintArray::from(524_314);
+        let tagged = ua.with_tag(0xAB);
+        assert_eq!(0xAB, tagged.tag());
+        assert_eq!(ua.len(), tagged.len());
+        assert_eq!(
+            ua.into_iter().collect::<Vec<u128>>(),
+            tagged.into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(0, ua.tag());
+        assert_eq!(ua.raw(), tagged.with_tag(0).raw());
+        assert_eq!(ua.raw(), tagged.normalize().raw());
+
+        // The tag stays in place when items are added.
+        let tagged = tagged.extend(vec![15; 25]);
+        assert_eq!(0xAB, tagged.tag());
+        assert_eq!(28, tagged.len());
+        assert_eq!(Some(15), tagged.at(27));
+
+        let full = UintArray::new::<u8>().extend(0..15);
+        assert_eq!(0, full.tag());
+        assert_eq!(
+            1,
+            UintArray::new::<u64>()
+                .append(u64::MAX as u128)
+                .with_tag(1)
+                .tag()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "with_tag: tag=256 does not fit in 8 bits (len=0, cap=15, size=8)")]
+    fn test_with_tag_does_not_fit() {
+        UintArray::new::<u8>().with_tag(1 << TAG_BITS);
+    }
+
+    #[test]
+    fn test_with_tag_shifting() {
+        let ua = UintArray::new::<u8>().extend(1..4).with_tag(7);
+        assert!(ua.is_valid());
+
+        let inserted = ua.insert(0, 9);
+        assert_eq!(7, inserted.tag());
+        assert_eq!(
+            vec![9, 1, 2, 3],
+            inserted.into_iter().collect::<Vec<u128>>()
+        );
+        assert!(inserted.is_valid());
+        assert_eq!(Ok(7), ua.try_insert(1, 9).map(|ua| ua.tag()));
+
+        let (popped, item) = ua.pop(0);
+        assert_eq!(Some(1), item);
+        assert_eq!(7, popped.tag());
+        assert_eq!(vec![2, 3], popped.into_iter().collect::<Vec<u128>>());
+        assert_eq!(7, ua.pop_front().0.tag());
+        assert_eq!(7, ua.pop_back().0.tag());
+
+        let removed = ua.remove(2);
+        assert_eq!(7, removed.tag());
+        assert_eq!(vec![1, 3], removed.into_iter().collect::<Vec<u128>>());
+
+        assert_eq!(7, ua.take(1).tag());
+        assert_eq!(7, ua.take(0).tag());
+        assert_eq!(7, ua.skip(1).tag());
+        assert_eq!(7, ua.clamp_each(2).tag());
+        assert_eq!(
+            vec![1, 2, 2],
+            ua.clamp_each(2).into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(7, ua.wrapping_add_scalar(1).tag());
+        assert_eq!(
+            7,
+            UintArray::new::<u8>()
+                .extend(vec![0, 1, 0])
+                .with_tag(7)
+                .trim()
+                .tag()
+        );
+
+        let shifted = ua.shift_right(1);
+        assert_eq!(7, shifted.tag());
+        assert_eq!(vec![0, 1, 2, 3], shifted.into_iter().collect::<Vec<u128>>());
+        let (shifted, carry) = ua.shift_in(9);
+        assert_eq!(0, carry);
+        assert_eq!(7, shifted.tag());
+        assert_eq!(vec![9, 1, 2, 3], shifted.into_iter().collect::<Vec<u128>>());
+
+        // The tag is dropped once the shifted items reach into it.
+        let full = UintArray::new::<u8>().extend(1..15).with_tag(7);
+        assert_eq!(0, full.shift_right(1).tag());
+        assert_eq!(Some(1), full.shift_right(1).at(1));
+        assert_eq!(0, full.shift_in(9).0.tag());
+
+        // Sizes 1 and 2 have room for the tag at the full length.
+        let bits = UintArray::new_size(1).extend(vec![1; 31]).with_tag(7);
+        assert_eq!(7, bits.tag());
+        assert_eq!(7, bits.pop(0).0.insert(0, 0).tag());
+        assert!(bits.is_valid());
+
+        // The tag doesn't keep the UintArray valid if other spare bits are set.
+        assert!(!ua.set_spare(1).with_tag(7).is_valid());
+    }
+
+    #[test]
+    fn test_with_tag_append() {
+        let ua = UintArray::new::<u8>().extend(0..13).with_tag(0xF0);
+
+        let full = ua.append(13);
+        assert_eq!(0xF0, full.tag());
+        assert_eq!(Some(13), full.at(13));
+        assert!(full.is_valid());
+        assert_eq!(0xF0, ua.extend(vec![13]).tag());
+        assert_eq!(0xF0, ua.append_repeat(13, 1).tag());
+        assert_eq!(
+            0xF0,
+            ua.with(|b| {
+                b.append(13);
+            })
+            .tag()
+        );
+
+        // A size-8 array with a tag is full at 14 items.
+        assert!(ua.can_fit(1));
+        assert!(!full.can_fit(1));
+        assert_eq!(Ok(0), full.fits_count(1));
+        assert_eq!(14, ua.extend_saturating(0..10).len());
+        assert_eq!(0xF0, ua.extend_saturating(0..10).tag());
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            full.try_append(1).map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "append: items would reach into the tag (len=14, cap=15, size=8)")]
+    fn test_with_tag_append_full() {
+        UintArray::new::<u8>()
+            .extend(0..14)
+            .with_tag(0xF0)
+            .append(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "extend: items would reach into the tag (len=14, cap=15, size=8)")]
+    fn test_with_tag_extend_full() {
+        let _ = UintArray::new::<u8>()
+            .extend(0..14)
+            .with_tag(0xF0)
+            .extend(vec![1]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "append_repeat: items would reach into the tag (len=14, cap=15, size=8)"
+    )]
+    fn test_with_tag_append_repeat_full() {
+        UintArray::new::<u8>()
+            .extend(0..14)
+            .with_tag(0xF0)
+            .append_repeat(1, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "append: items would reach into the tag (len=14, cap=15, size=8)")]
+    fn test_with_tag_with_full() {
+        UintArray::new::<u8>()
+            .extend(0..14)
+            .with_tag(0xF0)
+            .with(|b| {
+                b.append(1);
+            });
+    }
+
+    #[test]
+    #[should_panic(expected = "insert: shifting would overflow the uint (len=14, cap=15, size=8)")]
+    fn test_with_tag_insert_overflow() {
+        UintArray::new::<u8>()
+            .extend(1..15)
+            .with_tag(7)
+            .insert(0, 9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_tag_items_reach_tag() {
+        UintArray::new::<u8>().extend(0..15).with_tag(1);
+    }
+
+    #[test]
+    fn test_same_shape() {
+        let a = UintArray::new_size(4).extend(1..3);
+        let b = UintArray::from(524_314);
+        assert!(a.same_shape(&b));
+        assert!(b.same_shape(&a));
+        assert!(!a.same_shape(&UintArray::new::<u8>().extend(1..3)));
+    }
+
+    #[test]
+    fn test_eq_ignore_trailing_zeros() {
+        let a = UintArray::new::<u8>().extend(vec![1, 2]);
+        let b = UintArray::new::<u8>().extend(vec![1, 2, 0, 0]);
+        assert!(a.eq_ignore_trailing_zeros(&b));
+        assert!(b.eq_ignore_trailing_zeros(&a));
+        assert!(a.eq_ignore_trailing_zeros(&UintArray::new_size(4).extend(vec![1, 2, 0])));
+
+        let c = UintArray::new::<u8>().extend(vec![1, 0, 2]);
+        assert!(!c.eq_ignore_trailing_zeros(&a.take(1)));
+        assert!(!a.take(1).eq_ignore_trailing_zeros(&c));
+        assert!(!a.eq_ignore_trailing_zeros(&c));
+        assert!(a
+            .clear()
+            .eq_ignore_trailing_zeros(&b.clear().extend(vec![0, 0])));
+    }
+
     #[test]
     fn test_at() {
         // 524_314 = [0, 0, 8]
-        let ua = UintArray(524_314);
+        let ua = UintArray::from(524_314);
         assert_eq!(Some(8), ua.at(2));
     }
 
     #[test]
     fn test_at_out_of_bounds() {
-        let ua = UintArray(524_314);
+        let ua = UintArray::from(524_314);
         assert_eq!(None, ua.at(3));
     }
 
+    #[test]
+    fn test_at_with_offset() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(Some((8, 16)), ua.at_with_offset(2));
+        assert_eq!(Some((0, 8)), ua.at_with_offset(0));
+        assert_eq!(None, ua.at_with_offset(3));
+    }
+
+    #[test]
+    fn test_at_typed() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 255]);
+        assert_eq!(Some(255u8), ua.at_u8(1));
+        assert_eq!(None, ua.at_u8(2));
+
+        let ua = UintArray::new::<u16>().append(65535);
+        assert_eq!(Some(65535u16), ua.at_u16(0));
+
+        let ua = UintArray::new::<u32>().append(u32::MAX as u128);
+        assert_eq!(Some(u32::MAX), ua.at_u32(0));
+
+        let ua = UintArray::new::<u64>().append(u64::MAX as u128);
+        assert_eq!(Some(u64::MAX), ua.at_u64(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "at_u8: size is not 8 (len=1, cap=7, size=16)")]
+    fn test_at_typed_size_mismatch() {
+        UintArray::new::<u16>().append(1).at_u8(0);
+    }
+
     #[test]
     fn test_len() {
-        let ua = UintArray(524_314);
+        let ua = UintArray::from(524_314);
         assert_eq!(3, ua.len());
     }
 
+    #[test]
+    fn test_slot_offsets() {
+        let ua = UintArray::new_size(4).extend(1..4);
+        assert_eq!(vec![8, 12, 16], ua.slot_offsets().collect::<Vec<u128>>());
+        assert_eq!(0, ua.clear().slot_offsets().count());
+    }
+
+    #[test]
+    fn test_offsets_rev() {
+        let ua = UintArray::new_size(4).extend(1..4);
+        assert_eq!(vec![16, 12, 8], ua.offsets_rev().collect::<Vec<u128>>());
+        let mut offsets = ua.slot_offsets().collect::<Vec<u128>>();
+        offsets.reverse();
+        assert_eq!(offsets, ua.offsets_rev().collect::<Vec<u128>>());
+        assert_eq!(0, ua.clear().offsets_rev().count());
+    }
+
+    #[test]
+    fn test_masks() {
+        let ua = UintArray::new_size(4).extend(1..4);
+        assert_eq!(Some(0b1111 << 8), ua.masks().next());
+        assert_eq!(3, ua.masks().count());
+        for (mask, (pos, offset)) in ua.masks().zip(ua.slot_offsets().enumerate()) {
+            assert_eq!(ua.at(pos as u128), Some((ua.raw() & mask) >> offset));
+        }
+        assert_eq!(0, ua.clear().masks().count());
+        assert_eq!(
+            Some((u64::MAX as u128) << 8),
+            UintArray::new::<u64>().append(1).masks().next()
+        );
+    }
+
+    #[test]
+    fn test_would_append_at() {
+        let ua = UintArray::new_size(4).extend(1..3);
+        assert_eq!(Some(16), ua.would_append_at());
+        assert_eq!(Some(8), ua.clear().would_append_at());
+        assert_eq!(ua.append(3).slot_offsets().last(), ua.would_append_at());
+        assert_eq!(None, UintArray::new::<u64>().append(1).would_append_at());
+        assert_eq!(Some(8), UintArray::new::<u64>().would_append_at());
+        assert_eq!(
+            None,
+            UintArray::new_size(1).extend(vec![1; 31]).would_append_at()
+        );
+    }
+
     #[test]
     fn test_append() {
-        let ua = UintArray(524_314);
-        assert_eq!(4_718_626, ua.append(4).0);
+        let ua = UintArray::from(524_314);
+        assert_eq!(4_718_626, ua.append(4).raw());
     }
 
     #[test]
@@ -91,123 +757,1662 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_append_does_not_fit() {
-        let ua = UintArray(524_314);
+        let ua = UintArray::from(524_314);
 
         // ua.size() == 4
         ua.append(16);
     }
 
     #[test]
-    fn test_insert() {
-        let ua = UintArray(524_314);
-        assert_eq!(8_650_786, ua.insert(2, 4).0);
+    fn test_append_repeat() {
+        let ua = UintArray::new_size(4).append_repeat(7, 3);
+        assert_eq!(vec![7, 7, 7], ua.into_iter().collect::<Vec<u128>>());
+        assert_eq!(ua.raw(), ua.append_repeat(7, 0).raw());
+        assert_eq!(ua.append(1).append(1).raw(), ua.append_repeat(1, 2).raw());
     }
 
     #[test]
-    fn test_insert_append() {
-        let ua = UintArray(524_314);
+    #[should_panic]
+    fn test_append_repeat_exceed_capacity() {
+        UintArray::new::<u8>().append(1).append_repeat(1, 15);
+    }
 
-        // This is the same as appending
-        assert_eq!(4_718_626, ua.insert(3, 4).0);
+    #[test]
+    fn test_append_repeat_max_len() {
+        let ua = UintArray::new_size(1).append_repeat(1, 31);
+        assert_eq!(31, ua.len());
+        assert!(ua.is_valid());
+    }
+
+    #[test]
+    #[should_panic(expected = "append_repeat: capacity exceeded (len=0, cap=31, size=1)")]
+    fn test_append_repeat_exceed_max_len() {
+        UintArray::new_size(1).append_repeat(1, 40);
+    }
+
+    #[test]
+    #[should_panic(expected = "append_repeat: capacity exceeded")]
+    fn test_append_repeat_huge_count() {
+        UintArray::new_size(4).append(1).append_repeat(1, u128::MAX);
     }
 
     #[test]
     #[should_panic]
-    fn test_insert_exceed_capacity() {
-        let ua = UintArray::new::<u64>();
+    fn test_append_repeat_does_not_fit() {
+        UintArray::new_size(4).append_repeat(16, 2);
+    }
 
-        // ua.cap() == 1
-        ua.append(0).insert(0, 0);
+    #[test]
+    fn test_resize_elements() {
+        let ua = UintArray::from(524_314).append(15);
+        let grown = ua.resize_elements(8).unwrap();
+        assert_eq!(8, grown.size());
+        assert_eq!(vec![0, 0, 8, 15], grown.into_iter().collect::<Vec<u128>>());
+        assert_eq!(Ok(ua.raw()), grown.resize_elements(4).map(|ua| ua.raw()));
+
+        let ua = UintArray::new::<u8>().extend(vec![1, 16]);
+        assert_eq!(
+            Err(UintArrayError::ItemTooLarge),
+            ua.resize_elements(4).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            UintArray::new::<u8>()
+                .extend(0..3)
+                .resize_elements(64)
+                .map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::NotPowerOfTwo),
+            ua.resize_elements(12).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::InvalidSize),
+            ua.resize_elements(128).map(|ua| ua.raw())
+        );
     }
 
     #[test]
-    fn test_extend() {
-        let ua = UintArray(524_314);
-        assert_eq!(18_020_302_906, ua.extend(1..5).0);
+    fn test_push_growing() {
+        let ua = UintArray::new_size(4).extend(1..4);
+        assert_eq!(4, ua.push_growing(5).size());
+
+        let ua = ua.push_growing(20);
+        assert_eq!(8, ua.size());
+        assert_eq!(vec![1, 2, 3, 20], ua.into_iter().collect::<Vec<u128>>());
     }
 
     #[test]
-    #[should_panic]
-    fn test_extend_exceed_capacity() {
-        let ua = UintArray(524_314);
-        ua.extend((0..15).cycle().take(30));
+    #[should_panic(expected = "push_growing: capacity exceeded (len=0, cap=1, size=64)")]
+    fn test_push_growing_exceed_capacity() {
+        UintArray::new::<u32>().extend(0..3).push_growing(1 << 40);
     }
 
     #[test]
-    #[should_panic]
-    fn test_extend_beyond_capacity() {
-        let ua = UintArray(524_314);
-        ua.extend(0..100);
+    #[should_panic(expected = "append: capacity exceeded (len=1, cap=1, size=64)")]
+    fn test_append_exceed_capacity_message() {
+        UintArray::new::<u64>().append(0).append(0);
     }
 
     #[test]
-    #[should_panic]
-    fn test_extend_does_not_fit() {
-        let ua = UintArray(524_314);
-        ua.extend(16..);
+    #[should_panic(expected = "append: item=16 does not fit (len=3, cap=30, size=4)")]
+    fn test_append_does_not_fit_message() {
+        UintArray::from(524_314).append(16);
     }
 
     #[test]
-    fn test_clear() {
-        let ua = UintArray(524_314);
-        assert_eq!(2, ua.clear().0);
+    #[should_panic(expected = "insert: capacity exceeded (len=1, cap=1, size=64)")]
+    fn test_insert_exceed_capacity_message() {
+        UintArray::new::<u64>().append(0).insert(0, 0);
     }
 
     #[test]
-    fn test_remove() {
-        let ua = UintArray(524_314);
-        assert_eq!(524_314, ua.remove(2).0);
-        assert_eq!(32_786, ua.remove(0).0);
+    #[should_panic(expected = "insert: item=16 does not fit (len=3, cap=30, size=4)")]
+    fn test_insert_does_not_fit_message() {
+        UintArray::from(524_314).insert(1, 16);
     }
 
     #[test]
-    fn test_pop() {
-        let ua = UintArray(524_314);
-        let (ua, item) = ua.pop(1);
-        assert_eq!(Some(0), item);
-        assert_eq!(32_786, ua.0);
-        assert_eq!(2, ua.len());
+    #[should_panic(expected = "extend: capacity exceeded (len=3, cap=30, size=4)")]
+    fn test_extend_exceed_capacity_message() {
+        let _ = UintArray::from(524_314).extend(0..100);
+    }
 
-        let (ua, item) = ua.pop(2);
-        assert_eq!(None, item);
-        assert_eq!(32_786, ua.0);
-        assert_eq!(2, ua.len());
+    #[test]
+    #[should_panic(expected = "extend: item=16 does not fit (len=3, cap=30, size=4)")]
+    fn test_extend_does_not_fit_message() {
+        let _ = UintArray::from(524_314).extend(14..17);
     }
 
     #[test]
-    fn test_index() {
-        let ua = UintArray(524_314);
-        assert_eq!(Some(2), ua.index(8));
-        assert_eq!(None, ua.index(2));
+    fn test_insert() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(8_650_786, ua.insert(2, 4).raw());
     }
 
     #[test]
-    fn test_count() {
-        let ua = UintArray(524_314);
-        assert_eq!(2, ua.count(0));
-        assert_eq!(0, ua.count(2));
+    #[should_panic(expected = "insert: capacity exceeded (len=15, cap=15, size=8)")]
+    fn test_insert_full() {
+        UintArray::new::<u8>().extend(1..16).insert(0, 0);
     }
 
     #[test]
-    fn test_aggregate() {
-        let ua = UintArray(524_314);
-        assert_eq!(8, ua.aggregate(|x| x));
+    fn test_insert_nearly_full() {
+        let ua = UintArray::new::<u8>().extend(1..15).insert(0, 0);
+        assert_eq!(15, ua.len());
+        assert_eq!(Some(14), ua.at(14));
     }
 
     #[test]
-    fn test_iterator() {
-        // 1, 2, 3, 4
-        let ua = UintArray(4_399_394);
-        let mut i = 1;
-        for u in ua {
-            assert_eq!(i, u);
-            i += 1;
-        }
+    #[should_panic(expected = "insert: shifting would overflow the uint (len=14, cap=15, size=8)")]
+    fn test_insert_overflow() {
+        UintArray::new::<u8>()
+            .extend(1..15)
+            .set_spare(0xFF)
+            .insert(0, 0);
     }
 
     #[test]
-    fn test_format() {
-        let ua = UintArray(293399018589609169090056132135457263858);
-        assert_eq!(ua.format(), "1101 1100 1011 1010 1001 1000 0111 0110\n0101 0100 0011 0010 0001 0000 1111 1110\n1101 1100 1011 1010 1001 1000 0111 0110\n0101 0100 0011 0010 0001 0000 1111 0010\n");
+    fn test_insert_append() {
+        let ua = UintArray::from(524_314);
+
+        // This is the same as appending
+        assert_eq!(4_718_626, ua.insert(3, 4).raw());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_exceed_capacity() {
+        let ua = UintArray::new::<u64>();
+
+        // ua.cap() == 1
+        ua.append(0).insert(0, 0);
+    }
+
+    #[test]
+    fn test_with() {
+        let ua = UintArray::new::<u8>();
+        let built = ua.with(|b| {
+            b.append(1).append(2).append(3);
+        });
+        assert_eq!(ua.append(1).append(2).append(3).raw(), built.raw());
+
+        let ua = UintArray::from(524_314);
+        let built = ua.with(|b| {
+            assert_eq!(3, b.len());
+            b.extend(vec![4, 5]);
+            assert_eq!(5, b.len());
+        });
+        assert_eq!(ua.extend(vec![4, 5]).raw(), built.raw());
+        assert_eq!(ua.raw(), ua.with(|_| {}).raw());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_panic() {
+        UintArray::new::<u64>().with(|b| {
+            b.append(1).append(2);
+        });
+    }
+
+    #[test]
+    fn test_can_extend() {
+        let ua = UintArray::from(524_314);
+        assert!(ua.can_extend(vec![15, 0, 3]));
+        assert!(ua.can_extend(vec![]));
+        assert!(!ua.can_extend(vec![1, 16]));
+        assert!(!ua.can_extend(0..ua.cap()));
+        assert!(UintArray::new::<u16>().can_extend(0..7));
+    }
+
+    #[test]
+    fn test_can_fit() {
+        let ua = UintArray::from(524_314);
+        assert!(ua.can_fit(0));
+        assert!(ua.can_fit(ua.cap() - 3));
+        assert!(!ua.can_fit(ua.cap() - 2));
+        assert!(!UintArray::new::<u64>().append(0).can_fit(1));
+        assert!(!ua.can_fit(u128::MAX));
+
+        let ua = UintArray::new_size(1);
+        assert!(ua.can_fit(31));
+        assert!(!ua.can_fit(40));
+        assert!(ua.can_extend(vec![1; 31]));
+        assert!(!ua.can_extend(vec![1; 40]));
+    }
+
+    #[test]
+    fn test_fits_count() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(Ok(27), ua.fits_count(3));
+        assert_eq!(Ok(27), ua.fits_count(15));
+        assert_eq!(Err(UintArrayError::ItemTooLarge), ua.fits_count(99));
+        assert_eq!(Err(UintArrayError::ItemTooLarge), ua.fits_count(16));
+        assert_eq!(Ok(0), UintArray::new::<u64>().append(1).fits_count(1));
+        assert_eq!(Ok(15), UintArray::new::<u8>().fits_count(255));
+        assert_eq!(Ok(31), UintArray::new_size(1).fits_count(1));
+        assert_eq!(
+            Ok(21),
+            UintArray::new_size(2).extend(vec![3; 10]).fits_count(3)
+        );
+    }
+
+    #[test]
+    fn test_try_append() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(ua.append(4).raw(), ua.try_append(4).unwrap().raw());
+        assert_eq!(
+            Err(UintArrayError::ItemTooLarge),
+            ua.try_append(16).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            UintArray::new::<u64>()
+                .append(0)
+                .try_append(0)
+                .map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 2, 4]);
+        let sorted = ua.insert_sorted(3);
+        assert_eq!(vec![1, 2, 3, 4], sorted.into_iter().collect::<Vec<u128>>());
+
+        let sorted = ua.insert_sorted(0).insert_sorted(9).insert_sorted(2);
+        assert_eq!(
+            vec![0, 1, 2, 2, 4, 9],
+            sorted.into_iter().collect::<Vec<u128>>()
+        );
+
+        let sorted = ua.clear().insert_sorted(5);
+        assert_eq!(vec![5], sorted.into_iter().collect::<Vec<u128>>());
+    }
+
+    #[test]
+    fn test_try_insert() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(ua.insert(2, 4).raw(), ua.try_insert(2, 4).unwrap().raw());
+        assert_eq!(ua.append(4).raw(), ua.try_insert(3, 4).unwrap().raw());
+        assert_eq!(
+            Err(UintArrayError::ItemTooLarge),
+            ua.try_insert(0, 16).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            UintArray::new::<u64>()
+                .append(0)
+                .try_insert(0, 0)
+                .map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_try_insert_overflow() {
+        let ua = UintArray::new::<u8>().extend(1..15).set_spare(0xFF);
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            ua.try_insert(0, 0).map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_try_insert_out_of_bounds() {
+        let ua = UintArray::from(524_314);
+
+        // insert appends when the position is beyond the end, try_insert doesn't.
+        assert_eq!(ua.append(4).raw(), ua.insert(4, 4).raw());
+        assert_eq!(
+            Err(UintArrayError::OutOfBounds),
+            ua.try_insert(4, 4).map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_set_many() {
+        let ua = UintArray::from(524_314);
+        let set = ua.set_many(&[(0, 5), (2, 15)]).unwrap();
+        assert_eq!(Some(5), set.at(0));
+        assert_eq!(Some(0), set.at(1));
+        assert_eq!(Some(15), set.at(2));
+        assert_eq!(3, set.len());
+
+        assert_eq!(Ok(ua.raw()), ua.set_many(&[]).map(|ua| ua.raw()));
+        assert_eq!(
+            Ok(set.raw()),
+            ua.set_many(&[(0, 1), (2, 15), (0, 5)]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::OutOfBounds),
+            ua.set_many(&[(0, 1), (3, 1)]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::ItemTooLarge),
+            ua.set_many(&[(0, 16)]).map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_extend() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(18_020_302_906, ua.extend(1..5).raw());
+    }
+
+    #[test]
+    fn test_extend_trait() {
+        let mut ua = UintArray::from(524_314);
+        Extend::extend(&mut ua, 1..5);
+        assert_eq!(18_020_302_906, ua.raw());
+
+        // Extending in place one item at a time appends each item.
+        let mut ua = UintArray::new::<u8>();
+        for x in (1..4).map(|x| x * 2) {
+            Extend::extend(&mut ua, Some(x));
+        }
+        assert_eq!(vec![2, 4, 6], ua.into_iter().collect::<Vec<u128>>());
+
+        // Works anywhere an Extend is expected.
+        fn extend_twice<T: Extend<u128>>(t: &mut T) {
+            t.extend(1..3);
+            t.extend(vec![3]);
+        }
+
+        let mut ua = UintArray::new::<u8>();
+        extend_twice(&mut ua);
+        assert_eq!(vec![1, 2, 3], ua.into_iter().collect::<Vec<u128>>());
+    }
+
+    #[test]
+    fn test_extend_to_capacity() {
+        let ua = UintArray::new::<u32>().extend(1..4);
+        assert_eq!(3, ua.len());
+        assert_eq!(ua.raw(), ua.extend(0..0).raw());
+
+        let ua = UintArray::new::<u8>().extend(0..15);
+        assert_eq!(ua.raw(), ua.extend(0..0).raw());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_extend_exceed_capacity() {
+        let ua = UintArray::from(524_314);
+        let _ = ua.extend((0..15).cycle().take(30));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_extend_beyond_capacity() {
+        let ua = UintArray::from(524_314);
+        let _ = ua.extend(0..100);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_extend_does_not_fit() {
+        let ua = UintArray::from(524_314);
+        let _ = ua.extend(16..);
+    }
+
+    #[test]
+    fn test_extend_saturating() {
+        let ua = UintArray::from(524_314).extend_saturating((0..16).cycle());
+        assert_eq!(30, ua.len());
+        assert_eq!(Some(10), ua.at(29));
+
+        let ua = UintArray::new::<u8>().extend_saturating(1..4);
+        assert_eq!(UintArray::new::<u8>().extend(1..4).raw(), ua.raw());
+        assert_eq!(ua.raw(), ua.extend_saturating(0..0).raw());
+
+        for size in [1, 2].iter() {
+            let ua = UintArray::new_size(*size).extend_saturating(std::iter::repeat(1));
+            assert_eq!(31, ua.len(), "size={}", size);
+            assert!(ua.is_valid());
+            assert_eq!(ua.raw(), ua.extend_saturating(std::iter::repeat(1)).raw());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_extend_saturating_does_not_fit() {
+        UintArray::new_size(4).extend_saturating(14..17);
+    }
+
+    #[test]
+    fn test_extend_array() {
+        let a = UintArray::from(524_314);
+        let b = UintArray::new_size(4).extend(1..5);
+        assert_eq!(a.extend(b).raw(), a.extend_array(&b).raw());
+        assert_eq!(a.raw(), a.extend_array(&b.clear()).raw());
+        assert_eq!(b.raw(), b.clear().extend_array(&b).raw());
+
+        // Different sizes fall back to extending item by item.
+        let c = UintArray::new::<u8>().extend(1..5);
+        assert_eq!(a.extend(c).raw(), a.extend_array(&c).raw());
+    }
+
+    #[test]
+    #[should_panic(expected = "extend_array: capacity exceeded (len=8, cap=15, size=8)")]
+    fn test_extend_array_exceed_capacity() {
+        let ua = UintArray::new::<u8>().extend(0..8);
+        ua.extend_array(&ua);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_extend_array_does_not_fit() {
+        let ua = UintArray::new_size(4).extend(0..8);
+        ua.extend_array(&UintArray::new::<u8>().append(16));
+    }
+
+    #[test]
+    fn test_iter_from() {
+        let ua = UintArray::new::<u8>().extend(1..5);
+        assert_eq!(vec![3, 4], ua.iter_from(2).collect::<Vec<u128>>());
+        assert_eq!(vec![1, 2, 3, 4], ua.iter_from(0).collect::<Vec<u128>>());
+        assert_eq!(0, ua.iter_from(4).count());
+        assert_eq!(0, ua.iter_from(10).count());
+        assert_eq!(None, ua.iter_from(u128::MAX).next());
+        assert_eq!(0, ua.iter_from(u128::MAX).count());
+    }
+
+    #[test]
+    fn test_tail() {
+        let ua = UintArray::new::<u8>().extend(1..5);
+        assert_eq!(vec![3, 4], ua.tail(2).collect::<Vec<u128>>());
+        assert_eq!(vec![1, 2, 3, 4], ua.tail(4).collect::<Vec<u128>>());
+        assert_eq!(vec![1, 2, 3, 4], ua.tail(10).collect::<Vec<u128>>());
+        assert_eq!(0, ua.tail(0).count());
+        assert_eq!(0, ua.clear().tail(2).count());
+    }
+
+    #[test]
+    fn test_bits() {
+        let ua = UintArray::new_size(1).extend(vec![1, 0, 0, 1, 1]);
+        let bits = ua.bits().collect::<Vec<bool>>();
+        assert_eq!(vec![true, false, false, true, true], bits);
+        assert_eq!(0, ua.clear().bits().count());
+    }
+
+    #[test]
+    #[should_panic(expected = "bits: size is not 1 (len=0, cap=15, size=8)")]
+    fn test_bits_size_not_one() {
+        let _ = UintArray::new::<u8>().bits();
+    }
+
+    #[test]
+    fn test_range_iter() {
+        let ua = UintArray::new::<u8>().extend(1..5);
+        assert_eq!(vec![2, 3], ua.range_iter(1, 3).collect::<Vec<u128>>());
+        assert_eq!(vec![4], ua.range_iter(3, 10).collect::<Vec<u128>>());
+        assert_eq!(0, ua.range_iter(3, 1).count());
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let a = UintArray::new::<u8>().extend(vec![1, 3]);
+        let b = UintArray::new::<u8>().extend(vec![2, 4]);
+        let merged = a.merge_sorted(&b);
+        assert_eq!(vec![1, 2, 3, 4], merged.into_iter().collect::<Vec<u128>>());
+
+        let c = UintArray::new::<u8>().extend(vec![0, 3, 3, 9]);
+        let merged = a.merge_sorted(&c);
+        assert_eq!(
+            vec![0, 1, 3, 3, 3, 9],
+            merged.into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(a.raw(), a.merge_sorted(&a.clear()).raw());
+    }
+
+    #[test]
+    #[should_panic(expected = "merge_sorted: other size=16 does not match (len=0, cap=15, size=8)")]
+    fn test_merge_sorted_size_mismatch() {
+        UintArray::new::<u8>().merge_sorted(&UintArray::new::<u16>());
+    }
+
+    #[test]
+    #[should_panic(expected = "merge_sorted: capacity exceeded (len=0, cap=7, size=16)")]
+    fn test_merge_sorted_exceed_capacity() {
+        let ua = UintArray::new::<u16>().extend(1..5);
+        ua.merge_sorted(&ua);
+    }
+
+    #[test]
+    fn test_interleave() {
+        let a = UintArray::new::<u8>().extend(vec![1, 3]);
+        let b = UintArray::new::<u8>().extend(vec![2, 4]);
+        let interleaved = a.interleave(&b);
+        assert_eq!(
+            vec![1, 2, 3, 4],
+            interleaved.into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(
+            vec![2, 1, 4, 3],
+            b.interleave(&a).into_iter().collect::<Vec<u128>>()
+        );
+
+        let c = UintArray::new::<u8>().extend(vec![5, 6, 7]);
+        assert_eq!(
+            vec![1, 5, 3, 6, 7],
+            a.interleave(&c).into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(
+            vec![5, 1, 6, 3, 7],
+            c.interleave(&a).into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(a.raw(), a.interleave(&a.clear()).raw());
+        assert_eq!(a.raw(), a.clear().interleave(&a).raw());
+    }
+
+    #[test]
+    #[should_panic(expected = "interleave: other size=16 does not match (len=0, cap=15, size=8)")]
+    fn test_interleave_size_mismatch() {
+        UintArray::new::<u8>().interleave(&UintArray::new::<u16>());
+    }
+
+    #[test]
+    fn test_deinterleave() {
+        let ua = UintArray::new::<u8>().extend(1..5);
+        let (even, odd) = ua.deinterleave();
+        assert_eq!(vec![1, 3], even.into_iter().collect::<Vec<u128>>());
+        assert_eq!(vec![2, 4], odd.into_iter().collect::<Vec<u128>>());
+        assert_eq!(ua.raw(), even.interleave(&odd).raw());
+
+        let (even, odd) = ua.append(5).deinterleave();
+        assert_eq!(vec![1, 3, 5], even.into_iter().collect::<Vec<u128>>());
+        assert_eq!(vec![2, 4], odd.into_iter().collect::<Vec<u128>>());
+
+        let (even, odd) = ua.clear().deinterleave();
+        assert_eq!(ua.clear().raw(), even.raw());
+        assert_eq!(ua.clear().raw(), odd.raw());
+    }
+
+    #[test]
+    fn test_zip() {
+        let a = UintArray::new::<u8>().extend(1..4);
+        let b = UintArray::new_size(4).extend(4..6);
+        assert_eq!(
+            vec![(1, 4), (2, 5)],
+            a.zip(&b).collect::<Vec<(u128, u128)>>()
+        );
+        assert_eq!(
+            vec![(4, 1), (5, 2)],
+            b.zip(&a).collect::<Vec<(u128, u128)>>()
+        );
+        assert_eq!(0, a.zip(&a.clear()).count());
+    }
+
+    #[test]
+    fn test_zip_min_max() {
+        let a = UintArray::new::<u8>().extend(vec![1, 4, 2]);
+        let b = UintArray::new::<u8>().extend(vec![3, 2, 5]);
+        assert_eq!(
+            vec![3, 4, 5],
+            a.zip_max(&b).into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(
+            vec![1, 2, 2],
+            a.zip_min(&b).into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(a.zip_max(&b).raw(), b.zip_max(&a).raw());
+        assert_eq!(a.raw(), a.zip_min(&a).raw());
+
+        let c = UintArray::new::<u8>().extend(vec![9]);
+        assert_eq!(vec![9], a.zip_max(&c).into_iter().collect::<Vec<u128>>());
+        assert_eq!(a.clear().raw(), a.zip_min(&a.clear()).raw());
+    }
+
+    #[test]
+    #[should_panic(expected = "zip_max: other size=16 does not match (len=0, cap=15, size=8)")]
+    fn test_zip_max_size_mismatch() {
+        UintArray::new::<u8>().zip_max(&UintArray::new::<u16>());
+    }
+
+    #[test]
+    fn test_take() {
+        let ua = UintArray::new::<u8>().extend(1..5);
+        assert_eq!(vec![1, 2], ua.take(2).into_iter().collect::<Vec<u128>>());
+        assert_eq!(ua.raw(), ua.take(4).raw());
+        assert_eq!(ua.raw(), ua.take(10).raw());
+        assert_eq!(ua.clear().raw(), ua.take(0).raw());
+    }
+
+    #[test]
+    fn test_skip() {
+        let ua = UintArray::new::<u8>().extend(1..5);
+        assert_eq!(vec![3, 4], ua.skip(2).into_iter().collect::<Vec<u128>>());
+        assert_eq!(ua.raw(), ua.skip(0).raw());
+        assert_eq!(ua.clear().raw(), ua.skip(4).raw());
+        assert_eq!(ua.clear().raw(), ua.skip(10).raw());
+
+        let ua = UintArray::new::<u8>().extend(0..15);
+        assert_eq!(vec![14], ua.skip(14).into_iter().collect::<Vec<u128>>());
+    }
+
+    #[test]
+    fn test_shift_in() {
+        let ua = UintArray::new::<u32>().extend(1..4);
+        let (shifted, carry) = ua.shift_in(9);
+        assert_eq!(3, carry);
+        assert_eq!(vec![9, 1, 2], shifted.into_iter().collect::<Vec<u128>>());
+
+        let (shifted, carry) = ua.take(2).shift_in(9);
+        assert_eq!(0, carry);
+        assert_eq!(vec![9, 1, 2], shifted.into_iter().collect::<Vec<u128>>());
+
+        let (shifted, carry) = ua.clear().shift_in(9);
+        assert_eq!(0, carry);
+        assert_eq!(vec![9], shifted.into_iter().collect::<Vec<u128>>());
+
+        let ua = UintArray::new::<u64>().append(5);
+        let (shifted, carry) = ua.shift_in(u64::MAX as u128);
+        assert_eq!(5, carry);
+        assert_eq!(Some(u64::MAX as u128), shifted.at(0));
+        assert_eq!(1, shifted.len());
+    }
+
+    #[test]
+    fn test_shift_in_max_len() {
+        // Sizes 1 and 2 are full at 31 items, the largest length.
+        let ua = UintArray::new_size(1).extend(vec![0; 30]).append(1);
+        let (shifted, carry) = ua.shift_in(1);
+        assert_eq!(1, carry);
+        assert_eq!(31, shifted.len());
+        assert_eq!(Some(1), shifted.at(0));
+        assert_eq!(Some(0), shifted.at(30));
+    }
+
+    #[test]
+    #[should_panic(expected = "shift_in: item=16 does not fit (len=0, cap=30, size=4)")]
+    fn test_shift_in_does_not_fit() {
+        UintArray::new_size(4).shift_in(16);
+    }
+
+    #[test]
+    fn test_trim() {
+        let ua = UintArray::new::<u8>().extend(vec![0, 1, 2, 0, 0]);
+        let trimmed = ua.trim();
+        assert_eq!(vec![1, 2], trimmed.into_iter().collect::<Vec<u128>>());
+        assert_eq!(2, trimmed.len());
+        assert_eq!(trimmed.raw(), trimmed.trim().raw());
+
+        let ua = UintArray::new::<u8>().extend(vec![0, 1, 0, 2]);
+        assert_eq!(vec![1, 0, 2], ua.trim().into_iter().collect::<Vec<u128>>());
+        assert_eq!(ua.clear().raw(), ua.clear().extend(vec![0; 3]).trim().raw());
+        assert_eq!(ua.clear().raw(), ua.clear().trim().raw());
+    }
+
+    #[test]
+    fn test_rchunks() {
+        let ua = UintArray::new::<u8>().extend(1..4);
+        let chunks = ua.rchunks(2).collect::<Vec<Vec<u128>>>();
+        assert_eq!(vec![vec![2, 3], vec![1]], chunks);
+
+        let chunks = ua.append(4).rchunks(2).collect::<Vec<Vec<u128>>>();
+        assert_eq!(vec![vec![3, 4], vec![1, 2]], chunks);
+        assert_eq!(
+            vec![vec![1, 2, 3]],
+            ua.rchunks(5).collect::<Vec<Vec<u128>>>()
+        );
+        assert_eq!(0, ua.clear().rchunks(2).count());
+    }
+
+    #[test]
+    #[should_panic(expected = "rchunks: chunk size must be non-zero (len=0, cap=15, size=8)")]
+    fn test_rchunks_zero() {
+        let _ = UintArray::new::<u8>().rchunks(0);
+    }
+
+    #[test]
+    fn test_shift_left() {
+        let ua = UintArray::new::<u8>().extend(1..4);
+        assert_eq!(
+            vec![2, 3],
+            ua.shift_left(1).into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(ua.raw(), ua.shift_left(0).raw());
+        assert_eq!(0, ua.shift_left(5).len());
+    }
+
+    #[test]
+    fn test_shift_right() {
+        let ua = UintArray::new::<u8>().extend(1..4);
+        let shifted = ua.shift_right(1);
+        assert_eq!(vec![0, 1, 2, 3], shifted.into_iter().collect::<Vec<u128>>());
+        assert_eq!(ua.raw(), ua.shift_right(0).raw());
+
+        // Items beyond the capacity are dropped.
+        let ua = UintArray::new::<u32>().extend(1..4);
+        let shifted = ua.shift_right(1);
+        assert_eq!(vec![0, 1, 2], shifted.into_iter().collect::<Vec<u128>>());
+        let shifted = ua.shift_right(10);
+        assert_eq!(vec![0, 0, 0], shifted.into_iter().collect::<Vec<u128>>());
+        let shifted = ua.take(1).shift_right(u128::MAX);
+        assert_eq!(vec![0, 0, 0], shifted.into_iter().collect::<Vec<u128>>());
+
+        // Sizes 1 and 2 are full at 31 items, the largest length.
+        let shifted = UintArray::new_size(1).append(1).shift_right(40);
+        assert_eq!(31, shifted.len());
+        assert!(shifted.is_valid());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "extend: size exceeds the largest element size (len=0, cap=0, size=128)"
+    )]
+    fn test_extend_invalid_size() {
+        // The size bits are all set, which is a size of 128.
+        let ua = unsafe { UintArray::from_raw(0b111) };
+        let _ = ua.extend(0..1);
+    }
+
+    #[test]
+    fn test_clear() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(2, ua.clear().raw());
+    }
+
+    #[test]
+    fn test_reset() {
+        let ua = UintArray::from(524_314).reset();
+        assert_eq!(0, ua.len());
+        assert_eq!(1, ua.size());
+    }
+
+    #[test]
+    fn test_remove() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(524_314, ua.remove(2).raw());
+        assert_eq!(32_786, ua.remove(0).raw());
+    }
+
+    #[test]
+    fn test_pop() {
+        let ua = UintArray::from(524_314);
+        let (ua, item) = ua.pop(1);
+        assert_eq!(Some(0), item);
+        assert_eq!(32_786, ua.raw());
+        assert_eq!(2, ua.len());
+
+        let (ua, item) = ua.pop(2);
+        assert_eq!(None, item);
+        assert_eq!(32_786, ua.raw());
+        assert_eq!(2, ua.len());
+    }
+
+    #[test]
+    fn test_pop_back() {
+        let ua = UintArray::new::<u8>().extend(1..4);
+        let (popped, item) = ua.pop_back();
+        assert_eq!(Some(3), item);
+        assert_eq!(vec![1, 2], popped.into_iter().collect::<Vec<u128>>());
+        assert_eq!(ua.pop(2).0.raw(), popped.raw());
+
+        let (popped, item) = ua.clear().pop_back();
+        assert_eq!(None, item);
+        assert_eq!(ua.clear().raw(), popped.raw());
+    }
+
+    #[test]
+    fn test_pop_front() {
+        let ua = UintArray::new::<u8>().extend(1..4);
+        let (popped, item) = ua.pop_front();
+        assert_eq!(Some(1), item);
+        assert_eq!(vec![2, 3], popped.into_iter().collect::<Vec<u128>>());
+
+        let (popped, item) = popped.pop_front();
+        assert_eq!(Some(2), item);
+        assert_eq!(vec![3], popped.into_iter().collect::<Vec<u128>>());
+
+        let (popped, item) = ua.clear().pop_front();
+        assert_eq!(None, item);
+        assert_eq!(ua.clear().raw(), popped.raw());
+    }
+
+    #[test]
+    fn test_push_pop_back_mut() {
+        let mut ua = UintArray::new::<u8>();
+        ua.push(1);
+        ua.push(2);
+        ua.push(3);
+        assert_eq!(UintArray::new::<u8>().extend(1..4).raw(), ua.raw());
+
+        assert_eq!(Some(3), ua.pop_back_mut());
+        assert_eq!(Some(2), ua.pop_back_mut());
+        assert_eq!(UintArray::new::<u8>().append(1).raw(), ua.raw());
+        assert_eq!(Some(1), ua.pop_back_mut());
+        assert_eq!(None, ua.pop_back_mut());
+        assert_eq!(UintArray::new::<u8>().raw(), ua.raw());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_push_exceed_capacity() {
+        let mut ua = UintArray::new::<u64>();
+        ua.push(1);
+        ua.push(2);
+    }
+
+    #[test]
+    fn test_index() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(Some(2), ua.index(8));
+        assert_eq!(None, ua.index(2));
+    }
+
+    #[test]
+    fn test_rindex() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 2, 1, 3]);
+        assert_eq!(Some(2), ua.rindex(1));
+        assert_eq!(Some(3), ua.rindex(3));
+        assert_eq!(None, ua.rindex(4));
+    }
+
+    #[test]
+    fn test_indices_of() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 2, 1, 1]);
+        assert_eq!(vec![0, 2, 3], ua.indices_of(1));
+        assert_eq!(vec![1], ua.indices_of(2));
+        assert_eq!(Vec::<u128>::new(), ua.indices_of(3));
+        assert_eq!(ua.index(1), ua.indices_of(1).first().copied());
+        assert_eq!(ua.rindex(1), ua.indices_of(1).last().copied());
+        assert_eq!(Vec::<u128>::new(), ua.clear().indices_of(0));
+    }
+
+    #[test]
+    fn test_contains_subslice() {
+        let ua = UintArray::new::<u8>().extend(1..5);
+        let needle = UintArray::new::<u8>().extend(2..4);
+        assert_eq!(Some(1), ua.contains_subslice(&needle));
+        assert_eq!(Some(0), ua.contains_subslice(&ua));
+        assert_eq!(Some(0), ua.contains_subslice(&ua.clear()));
+        assert_eq!(Some(3), ua.contains_subslice(&ua.clear().append(4)));
+        assert_eq!(None, ua.contains_subslice(&ua.clear().extend(vec![2, 4])));
+        assert_eq!(None, ua.contains_subslice(&ua.append(5)));
+        assert_eq!(None, needle.contains_subslice(&ua));
+
+        // Items past the length must not match.
+        let ua = UintArray::new_size(4).extend(vec![1, 0]).take(1);
+        let needle = UintArray::new_size(4).extend(vec![1, 0]);
+        assert_eq!(None, ua.contains_subslice(&needle));
+
+        let full = UintArray::new::<u8>().extend(0..15);
+        assert_eq!(Some(0), full.contains_subslice(&full));
+        assert_eq!(Some(14), full.contains_subslice(&full.skip(14)));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "contains_subslice: needle size=16 does not match (len=0, cap=15, size=8)"
+    )]
+    fn test_contains_subslice_size_mismatch() {
+        UintArray::new::<u8>().contains_subslice(&UintArray::new::<u16>());
+    }
+
+    #[test]
+    fn test_count() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(2, ua.count(0));
+        assert_eq!(0, ua.count(2));
+    }
+
+    #[test]
+    fn test_leading_zeros_count() {
+        let ua = UintArray::new::<u8>().extend(vec![0, 0, 1, 2]);
+        assert_eq!(2, ua.leading_zeros_count());
+        assert_eq!(0, ua.skip(2).leading_zeros_count());
+        assert_eq!(3, ua.clear().extend(vec![0, 0, 0]).leading_zeros_count());
+        assert_eq!(0, ua.clear().leading_zeros_count());
+    }
+
+    #[test]
+    fn test_trailing_zeros_count() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 0, 0]);
+        assert_eq!(2, ua.trailing_zeros_count());
+        assert_eq!(0, ua.append(1).trailing_zeros_count());
+        assert_eq!(4, ua.clear().extend(vec![0; 4]).trailing_zeros_count());
+        assert_eq!(0, ua.clear().trailing_zeros_count());
+    }
+
+    #[test]
+    fn test_count_many() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 2, 1, 3]);
+        assert_eq!(vec![2, 1, 1], ua.count_many(&[1, 2, 3]));
+        assert_eq!(vec![0, 2, 2], ua.count_many(&[4, 1, 1]));
+        assert!(ua.count_many(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_histogram() {
+        let histogram = UintArray::new::<u8>().extend(vec![1, 1, 2]).histogram();
+        assert_eq!(2, histogram.len());
+        assert_eq!(Some(&2), histogram.get(&1));
+        assert_eq!(Some(&1), histogram.get(&2));
+        assert!(UintArray::new::<u8>().histogram().is_empty());
+    }
+
+    #[test]
+    fn test_distinct_count() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 2, 1, 3, 3]);
+        assert_eq!(3, ua.distinct_count());
+
+        let ua = UintArray::new_size(4).extend(vec![1, 2, 1, 3, 3, 15]);
+        assert_eq!(4, ua.distinct_count());
+        assert_eq!(0, ua.clear().distinct_count());
+    }
+
+    #[test]
+    fn test_mode() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 2, 2, 3, 2]);
+        assert_eq!(Some(2), ua.mode());
+        assert_eq!(Some(1), ua.extend(vec![1, 1]).mode());
+        assert_eq!(None, ua.clear().mode());
+    }
+
+    #[test]
+    fn test_presence_mask() {
+        let ua = UintArray::new_size(4).extend(vec![1, 3, 5]);
+        assert_eq!(0b101010, ua.presence_mask());
+        assert_eq!(0b101010, ua.extend(vec![5, 1]).presence_mask());
+        assert_eq!(0, ua.clear().presence_mask());
+        assert_eq!(1 << 15 | 1, ua.clear().extend(vec![0, 15]).presence_mask());
+        assert_eq!(1 << 127, UintArray::new::<u8>().append(127).presence_mask());
+    }
+
+    #[test]
+    fn test_presence_mask_does_not_fit() {
+        // Items without a bit in the mask are ignored, like in find_value_gap.
+        let ua = UintArray::new::<u8>().extend(vec![128, 0, 255]);
+        assert_eq!(1, ua.presence_mask());
+        assert_eq!(Some(1), ua.find_value_gap());
+        assert_eq!(
+            0,
+            UintArray::new::<u64>()
+                .append(u64::MAX as u128)
+                .presence_mask()
+        );
+    }
+
+    #[test]
+    fn test_find_value_gap() {
+        let ua = UintArray::new::<u8>().extend(vec![0, 1, 3]);
+        assert_eq!(Some(2), ua.find_value_gap());
+        assert_eq!(Some(4), ua.append(2).find_value_gap());
+        assert_eq!(Some(0), ua.clear().find_value_gap());
+        assert_eq!(Some(0), ua.clear().extend(vec![200, 1]).find_value_gap());
+
+        let ua = UintArray::new_size(1).extend(vec![1, 0]);
+        assert_eq!(None, ua.find_value_gap());
+    }
+
+    #[test]
+    fn test_try_for_each() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 2, 3, 4]);
+        let mut seen = Vec::new();
+        let result = ua.try_for_each(|x| {
+            seen.push(x);
+            if x % 2 == 0 {
+                Err(format!("{} is even", x))
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(Err(String::from("2 is even")), result);
+        assert_eq!(vec![1, 2], seen);
+
+        let result: Result<(), u128> = ua.try_for_each(|_| Ok(()));
+        assert_eq!(Ok(()), result);
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(8, ua.aggregate(|x| x));
+    }
+
+    #[test]
+    fn test_product() {
+        let ua = UintArray::new::<u8>().extend(1..5);
+        assert_eq!(24, ua.product());
+        assert_eq!(0, ua.append(0).product());
+        assert_eq!(1, ua.clear().product());
+
+        let ua = UintArray::new::<u8>().extend(vec![255; 15]);
+        assert_eq!(255u128.pow(15), ua.product());
+    }
+
+    #[test]
+    fn test_clamp_each() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 9, 3]).clamp_each(5);
+        assert_eq!(vec![1, 5, 3], ua.into_iter().collect::<Vec<u128>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "clamp_each: max=16 does not fit (len=1, cap=30, size=4)")]
+    fn test_clamp_each_does_not_fit() {
+        UintArray::new_size(4).append(1).clamp_each(16);
+    }
+
+    #[test]
+    fn test_to_u8_vec() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 2, 255]);
+        assert_eq!(Ok(vec![1, 2, 255]), ua.to_u8_vec());
+    }
+
+    #[test]
+    fn test_to_vec_size_mismatch() {
+        let ua = UintArray::new::<u16>().extend(1..4);
+        assert_eq!(Err(UintArrayError::SizeMismatch), ua.to_u8_vec());
+        assert_eq!(Err(UintArrayError::SizeMismatch), ua.to_u32_vec());
+        assert_eq!(Ok(vec![1, 2, 3]), ua.to_u16_vec());
+    }
+
+    #[test]
+    #[cfg(feature = "base64")]
+    fn test_base64_round_trip() {
+        let ua = UintArray::from(524_314);
+        let encoded = ua.to_base64();
+        assert_eq!("GgAIAAAAAAAAAAAAAAAAAA", encoded);
+        assert_eq!(ua.raw(), UintArray::from_base64(&encoded).unwrap().raw());
+
+        let ua = UintArray::from(u128::MAX << 8 | 31 << 3);
+        assert_eq!(
+            ua.raw(),
+            UintArray::from_base64(&ua.to_base64()).unwrap().raw()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "base64")]
+    fn test_base64_invalid() {
+        assert_eq!(
+            Err(UintArrayError::InvalidBase64),
+            UintArray::from_base64("GgAI").map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::InvalidBase64),
+            UintArray::from_base64("GgAIAAAAAAAAAAAAAAAAA=").map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::InvalidBase64),
+            UintArray::from_base64("GgAIAAAAAAAAAAAAAAAAAE").map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::LengthExceedsCapacity),
+            UintArray::from_base64(&unsafe { UintArray::from_raw(69421) }.to_base64())
+                .map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::InvalidSize),
+            UintArray::from_base64(&unsafe { UintArray::from_raw(7) }.to_base64())
+                .map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_to_bytes() {
+        let ua = UintArray::new::<u8>().extend(1..4);
+        assert_eq!(vec![1, 2, 3], ua.to_bytes());
+
+        let ua = UintArray::new::<u32>().append(0x01020304);
+        assert_eq!(vec![4, 3, 2, 1], ua.to_bytes());
+        assert!(ua.clear().to_bytes().is_empty());
+    }
+
+    #[test]
+    fn test_to_u64() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(Ok(524_314), ua.to_u64());
+        assert_eq!(Ok(524_314), ua.set_spare(1).to_u64());
+        assert_eq!(Ok(ua.raw() as u64), ua.to_u64());
+
+        // 8 + 14 * 4 = 64 bits fit exactly, one more item doesn't.
+        let ua = UintArray::new_size(4).extend(vec![15; 14]);
+        assert_eq!(Ok(ua.raw() as u64), ua.to_u64());
+        assert_eq!(Err(UintArrayError::DoesNotFit), ua.append(0).to_u64());
+        assert_eq!(
+            Err(UintArrayError::DoesNotFit),
+            UintArray::new::<u64>().append(1).to_u64()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "to_bytes: size is not a multiple of 8 (len=3, cap=30, size=4)")]
+    fn test_to_bytes_size_not_bytes() {
+        UintArray::from(524_314).to_bytes();
+    }
+
+    #[test]
+    fn test_try_to_array() {
+        let ua = UintArray::new::<u8>().extend(1..4);
+        assert_eq!(Ok([1, 2, 3]), ua.try_to_array::<3>());
+        assert_eq!(Ok([]), ua.clear().try_to_array::<0>());
+    }
+
+    #[test]
+    fn test_try_to_array_length_mismatch() {
+        let ua = UintArray::new::<u8>().extend(1..4);
+        assert_eq!(Err(UintArrayError::LengthMismatch), ua.try_to_array::<2>());
+        assert_eq!(Err(UintArrayError::LengthMismatch), ua.try_to_array::<4>());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("[0, 0, 8]", UintArray::from(524_314).to_string());
+        assert_eq!("[]", UintArray::new::<u8>().to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let ua: UintArray = "[1, 2, 3]".parse().unwrap();
+        assert_eq!(2, ua.size());
+        assert_eq!(vec![1, 2, 3], ua.into_iter().collect::<Vec<u128>>());
+
+        let ua: UintArray = " [0,0,8] ".parse().unwrap();
+        assert_eq!(UintArray::from(524_314).to_string(), ua.to_string());
+
+        let ua: UintArray = "[]".parse().unwrap();
+        assert_eq!(0, ua.len());
+
+        let ua: UintArray = format!("[{}]", vec!["255"; 15].join(", ")).parse().unwrap();
+        assert_eq!(8, ua.size());
+        assert_eq!(15, ua.len());
+
+        let ua: UintArray = format!("[{}]", vec!["1"; 31].join(", ")).parse().unwrap();
+        assert_eq!(1, ua.size());
+        assert_eq!(31, ua.len());
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert_eq!(
+            Err(UintArrayError::InvalidFormat),
+            "1, 2, 3".parse::<UintArray>().map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::InvalidFormat),
+            "[1, two, 3]".parse::<UintArray>().map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::InvalidFormat),
+            "[1, 2,]".parse::<UintArray>().map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::InvalidFormat),
+            "[+1]".parse::<UintArray>().map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::ItemTooLarge),
+            format!("[{}]", u128::MAX)
+                .parse::<UintArray>()
+                .map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            format!("[{}]", vec!["255"; 16].join(", "))
+                .parse::<UintArray>()
+                .map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            format!("[{}]", vec!["1"; 40].join(", "))
+                .parse::<UintArray>()
+                .map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_fixed() {
+        let ua = FixedUintArray::<4>::new().append(1).append(3).insert(1, 2);
+        assert_eq!(3, ua.len());
+        assert_eq!(Some(2), ua.at(1));
+        assert_eq!(vec![1, 2, 3], ua.into_iter().collect::<Vec<u128>>());
+        assert_eq!(4, UintArray::from(ua).size());
+    }
+
+    #[test]
+    fn test_fixed_capacity() {
+        assert_eq!(30, FixedUintArray::<4>::CAPACITY);
+        assert_eq!(1, FixedUintArray::<64>::CAPACITY);
+        assert_eq!(31, FixedUintArray::<1>::CAPACITY);
+        assert_eq!(31, FixedUintArray::<2>::CAPACITY);
+        assert_eq!(UintArray::new::<u8>().cap(), FixedUintArray::<8>::CAPACITY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fixed_append_does_not_fit() {
+        FixedUintArray::<4>::new().append(16);
+    }
+
+    #[test]
+    fn test_fixed_try_from() {
+        assert!(FixedUintArray::<4>::try_from(UintArray::from(524_314)).is_ok());
+        assert_eq!(
+            Err(UintArrayError::SizeMismatch),
+            FixedUintArray::<8>::try_from(UintArray::from(524_314)).map(|ua| ua.len())
+        );
+    }
+
+    #[test]
+    fn test_to_set() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 2, 1, 3, 2]);
+        assert_eq!(
+            vec![1, 2, 3],
+            ua.to_set().into_iter().collect::<Vec<u128>>()
+        );
+
+        let ua = UintArray::from(524_314);
+        assert_eq!(vec![0, 8], ua.to_set().into_iter().collect::<Vec<u128>>());
+        assert_eq!(4, ua.to_set().size());
+        assert_eq!(0, ua.clear().to_set().len());
+    }
+
+    #[test]
+    fn test_partition() {
+        let ua = UintArray::new::<u8>().extend(1..5);
+        let (even, odd) = ua.partition(|x| x % 2 == 0);
+        assert_eq!(vec![2, 4], even.into_iter().collect::<Vec<u128>>());
+        assert_eq!(vec![1, 3], odd.into_iter().collect::<Vec<u128>>());
+        assert_eq!(8, even.size());
+        assert_eq!(8, odd.size());
+
+        let (all, none) = ua.partition(|_| true);
+        assert_eq!(ua.raw(), all.raw());
+        assert_eq!(0, none.len());
+    }
+
+    #[test]
+    fn test_filter_frequent() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 2, 2, 3, 2]);
+        let frequent = ua.filter_frequent(2);
+        assert_eq!(vec![2, 2, 2], frequent.into_iter().collect::<Vec<u128>>());
+        assert_eq!(8, frequent.size());
+
+        let ua = ua.extend(vec![1, 3]);
+        assert_eq!(
+            vec![1, 2, 2, 3, 2, 1, 3],
+            ua.filter_frequent(2).into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(ua.raw(), ua.filter_frequent(0).raw());
+        assert_eq!(ua.clear().raw(), ua.filter_frequent(4).raw());
+    }
+
+    #[test]
+    fn test_wrapping_add_scalar() {
+        let ua = UintArray::new_size(4).extend(vec![14, 15]);
+        let added = ua.wrapping_add_scalar(1);
+        assert_eq!(vec![15, 0], added.into_iter().collect::<Vec<u128>>());
+        assert_eq!(2, added.len());
+        assert_eq!(ua.raw(), ua.wrapping_add_scalar(16).raw());
+
+        let added = UintArray::new::<u8>()
+            .extend(vec![0, 255])
+            .wrapping_add_scalar(u128::MAX);
+        assert_eq!(vec![255, 254], added.into_iter().collect::<Vec<u128>>());
+    }
+
+    #[test]
+    fn test_checked_add_scalar() {
+        let ua = UintArray::new_size(4).extend(vec![1, 13, 14]);
+        assert_eq!(
+            Ok(vec![2, 14, 15]),
+            ua.checked_add_scalar(1)
+                .map(|ua| ua.into_iter().collect::<Vec<u128>>())
+        );
+        assert_eq!(Ok(ua.raw()), ua.checked_add_scalar(0).map(|ua| ua.raw()));
+        assert_eq!(
+            Err(UintArrayError::ItemTooLarge),
+            ua.checked_add_scalar(2).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::ItemTooLarge),
+            ua.checked_add_scalar(u128::MAX).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Ok(ua.clear().raw()),
+            ua.clear().checked_add_scalar(u128::MAX).map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_wrapping_sub_scalar() {
+        let ua = UintArray::new_size(4).extend(vec![0, 1]);
+        let subtracted = ua.wrapping_sub_scalar(1);
+        assert_eq!(vec![15, 0], subtracted.into_iter().collect::<Vec<u128>>());
+        assert_eq!(ua.raw(), subtracted.wrapping_add_scalar(1).raw());
+        assert_eq!(ua.raw(), ua.wrapping_sub_scalar(32).raw());
+    }
+
+    #[test]
+    fn test_wrapping_mul_scalar() {
+        let ua = UintArray::new_size(4).extend(1..4);
+        let multiplied = ua.wrapping_mul_scalar(5);
+        assert_eq!(
+            vec![5, 10, 15],
+            multiplied.into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(3, multiplied.len());
+        assert_eq!(ua.raw(), ua.wrapping_mul_scalar(1).raw());
+        assert_eq!(ua.raw(), ua.wrapping_mul_scalar(17).raw());
+        assert_eq!(
+            vec![0, 0, 0],
+            ua.wrapping_mul_scalar(16)
+                .into_iter()
+                .collect::<Vec<u128>>()
+        );
+
+        let multiplied = UintArray::new::<u64>()
+            .append(u64::MAX as u128)
+            .wrapping_mul_scalar(u128::MAX);
+        assert_eq!(vec![1], multiplied.into_iter().collect::<Vec<u128>>());
+    }
+
+    #[test]
+    fn test_rotate_bits_each() {
+        let ua = UintArray::new_size(4).append(0b0001);
+        assert_eq!(Some(0b0010), ua.rotate_bits_each(1).at(0));
+
+        let ua = UintArray::new_size(4).extend(vec![0b0001, 0b1001, 0b1111]);
+        let rotated = ua.rotate_bits_each(3);
+        assert_eq!(
+            vec![0b1000, 0b1100, 0b1111],
+            rotated.into_iter().collect::<Vec<u128>>()
+        );
+        assert_eq!(3, rotated.len());
+        assert_eq!(ua.raw(), ua.rotate_bits_each(0).raw());
+        assert_eq!(ua.raw(), ua.rotate_bits_each(4).raw());
+        assert_eq!(ua.raw(), rotated.rotate_bits_each(1).raw());
+
+        let ua = UintArray::new::<u64>().append(1 << 63);
+        assert_eq!(Some(1), ua.rotate_bits_each(1).at(0));
+    }
+
+    #[test]
+    fn test_checksum() {
+        let ua = UintArray::from(524_314);
+        assert_eq!(0x1A ^ 0x08, ua.checksum());
+
+        // Spare bits don't affect the checksum.
+        assert_eq!(ua.checksum(), ua.set_spare(0xFFFF).checksum());
+        assert_ne!(ua.checksum(), ua.append(1).checksum());
+    }
+
+    #[test]
+    fn test_rle() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 1, 2, 3, 3, 3]);
+        assert_eq!(vec![(1, 2), (2, 1), (3, 3)], ua.rle());
+        assert_eq!(vec![(0, 2), (8, 1)], UintArray::from(524_314).rle());
+        assert!(ua.clear().rle().is_empty());
+    }
+
+    #[test]
+    fn test_group_runs() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 1, 2]);
+        assert_eq!(
+            vec![(1, 2), (2, 1)],
+            ua.group_runs().collect::<Vec<(u128, u128)>>()
+        );
+        assert_eq!(Some((1, 2)), ua.group_runs().next());
+        assert_eq!(
+            vec![(1, 1), (2, 1), (1, 1)],
+            ua.skip(1)
+                .append(1)
+                .group_runs()
+                .collect::<Vec<(u128, u128)>>()
+        );
+        assert_eq!(0, ua.clear().group_runs().count());
+    }
+
+    #[test]
+    fn test_from_rle() {
+        let ua = UintArray::new::<u8>().extend(vec![1, 1, 2, 3, 3, 3]);
+        assert_eq!(ua.raw(), UintArray::from_rle(8, &ua.rle()).unwrap().raw());
+        assert_eq!(0, UintArray::from_rle(8, &[]).unwrap().len());
+        assert_eq!(0, UintArray::from_rle(8, &[(5, 0)]).unwrap().len());
+    }
+
+    #[test]
+    fn test_from_rle_invalid() {
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            UintArray::from_rle(8, &[(1, 10), (2, 6)]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            UintArray::from_rle(8, &[(1, u128::MAX), (2, 1)]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::CapacityExceeded),
+            UintArray::from_rle(2, &[(3, 40)]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Ok(31),
+            UintArray::from_rle(2, &[(3, 30), (1, 1)]).map(|ua| ua.len())
+        );
+        assert_eq!(
+            Err(UintArrayError::ItemTooLarge),
+            UintArray::from_rle(4, &[(16, 1)]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::NotPowerOfTwo),
+            UintArray::from_rle(3, &[]).map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_iterator() {
+        // 1, 2, 3, 4
+        let ua = UintArray::from(4_399_394);
+        for (i, u) in (1..).zip(ua) {
+            assert_eq!(i, u);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unnecessary_fold)]
+    fn test_iterator_fold() {
+        // 1, 2, 3, 4
+        let ua = UintArray::from(4_399_394);
+        assert_eq!(10, ua.into_iter().fold(0, |a, b| a + b));
+        assert_eq!(10, ua.into_iter().sum::<u128>());
+        assert_eq!(vec![1, 2, 3, 4], ua.into_iter().collect::<Vec<u128>>());
+        assert_eq!(1234, ua.into_iter().fold(0, |a, b| a * 10 + b));
+        assert_eq!(34, ua.iter_from(2).fold(0, |a, b| a * 10 + b));
+        assert_eq!(0, ua.iter_from(10).fold(0, |a, b| a * 10 + b));
+
+        let mut iter = ua.into_iter();
+        iter.next();
+        assert_eq!(vec![2, 3, 4], iter.collect::<Vec<u128>>());
+    }
+
+    #[test]
+    fn test_format() {
+        let ua = UintArray::from(293399018589609169090056132135457263858);
+        assert_eq!(ua.format(), "1101 1100 1011 1010 1001 1000 0111 0110\n0101 0100 0011 0010 0001 0000 1111 1110\n1101 1100 1011 1010 1001 1000 0111 0110\n0101 0100 0011 0010 0001 0000 1111 0010\n");
+    }
+
+    #[test]
+    fn test_from_bits() {
+        let ua = UintArray::from(293399018589609169090056132135457263858);
+        assert_eq!(
+            Ok(ua.raw()),
+            UintArray::from_bits(&ua.format()).map(|ua| ua.raw())
+        );
+
+        let ua = UintArray::from(524_314);
+        assert_eq!(
+            Ok(ua.raw()),
+            UintArray::from_bits(&ua.format()).map(|ua| ua.raw())
+        );
+
+        let bits = format!("{:0128b}", ua.raw());
+        assert_eq!(Ok(ua.raw()), UintArray::from_bits(&bits).map(|ua| ua.raw()));
+
+        assert_eq!(
+            Err(UintArrayError::InvalidFormat),
+            UintArray::from_bits(&bits[1..]).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::InvalidFormat),
+            UintArray::from_bits(&format!("{}0", bits)).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::InvalidFormat),
+            UintArray::from_bits(&bits.replace('1', "2")).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::InvalidSize),
+            UintArray::from_bits(&format!("{:0128b}", 7)).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::LengthExceedsCapacity),
+            UintArray::from_bits(&format!("{:0128b}", 0b11110110)).map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_json_number() {
+        let ua = UintArray::from(524_314);
+        assert_eq!("\"524314\"", ua.to_json_number());
+        assert_eq!(
+            Ok(ua.raw()),
+            UintArray::from_json_number(&ua.to_json_number()).map(|ua| ua.raw())
+        );
+
+        let ua = UintArray::new::<u64>().append(u64::MAX as u128);
+        assert_eq!(
+            Ok(ua.raw()),
+            UintArray::from_json_number(&ua.to_json_number()).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Ok(ua.raw()),
+            UintArray::from_json_number(&ua.raw().to_string()).map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_from_json_number_invalid() {
+        for json in &["", "\"\"", "\"+1\"", "\"-1\"", "\"1.0\"", "\"12", "abc"] {
+            assert_eq!(
+                Err(UintArrayError::InvalidFormat),
+                UintArray::from_json_number(json).map(|ua| ua.raw())
+            );
+        }
+        assert_eq!(
+            Err(UintArrayError::InvalidFormat),
+            UintArray::from_json_number(&format!("\"{}0\"", u128::MAX)).map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::InvalidSize),
+            UintArray::from_json_number("\"7\"").map(|ua| ua.raw())
+        );
+        assert_eq!(
+            Err(UintArrayError::LengthExceedsCapacity),
+            UintArray::from_json_number("\"246\"").map(|ua| ua.raw())
+        );
+    }
+
+    #[test]
+    fn test_format_indexed() {
+        let ua = UintArray::new::<u8>().extend(1..4);
+        assert_eq!("[0]=1 [1]=2 [2]=3", ua.format_indexed());
+        assert_eq!("", ua.clear().format_indexed());
+    }
+
+    #[test]
+    fn test_error_display() {
+        let cases = [
+            (UintArrayError::CapacityExceeded, "capacity exceeded"),
+            (UintArrayError::ItemTooLarge, "item does not fit in size"),
+            (UintArrayError::OutOfBounds, "position out of bounds"),
+            (
+                UintArrayError::InvalidSize,
+                "size exceeds the largest element size",
+            ),
+            (UintArrayError::NotPowerOfTwo, "size is not a power of 2"),
+            (
+                UintArrayError::LengthExceedsCapacity,
+                "length exceeds capacity",
+            ),
+            (
+                UintArrayError::SizeMismatch,
+                "size does not match the required size",
+            ),
+            (
+                UintArrayError::LengthMismatch,
+                "length does not match the required length",
+            ),
+            (UintArrayError::InvalidBase64, "invalid base64"),
+            (UintArrayError::InvalidFormat, "invalid list format"),
+            (
+                UintArrayError::DoesNotFit,
+                "does not fit in the requested type",
+            ),
+        ];
+
+        for (error, message) in cases.iter() {
+            assert_eq!(*message, error.to_string());
+        }
+    }
+
+    #[test]
+    fn test_error_is_error() {
+        let error: Box<dyn std::error::Error> = Box::new(UintArrayError::OutOfBounds);
+        assert_eq!("position out of bounds", error.to_string());
+    }
+
+    #[test]
+    fn test_reverse_new() {
+        let ua = ReverseUintArray::new::<u8>();
+        assert_eq!(8, ua.size());
+        assert_eq!(15, ua.cap());
+        assert!(ua.is_empty());
+        assert_eq!(UintArray::new::<u8>().raw(), ua.raw());
+    }
+
+    #[test]
+    fn test_reverse_append() {
+        let ua = ReverseUintArray::new_size(4).append(0).append(0).append(8);
+        assert_eq!(3, ua.len());
+        assert_eq!(0x008 << 116 | 3 << 3 | 2, ua.raw());
+    }
+
+    #[test]
+    fn test_reverse_at() {
+        let ua = ReverseUintArray::new_size(4).append(1).append(2).append(3);
+        assert_eq!(Some(1), ua.at(0));
+        assert_eq!(Some(3), ua.at(2));
+        assert_eq!(None, ua.at(3));
+        assert_eq!(vec![1, 2, 3], ua.into_iter().collect::<Vec<u128>>());
+
+        let mut iter = ua.into_iter();
+        assert_eq!(3, iter.by_ref().count());
+        assert_eq!(None, iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_reverse_full() {
+        let mut ua = ReverseUintArray::new::<u8>();
+        for i in 0..15 {
+            ua = ua.append(i + 1);
+        }
+        assert_eq!(
+            (1..16).collect::<Vec<u128>>(),
+            ua.into_iter().collect::<Vec<u128>>()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "append: capacity exceeded (len=1, cap=1, size=64)")]
+    fn test_reverse_append_exceed_capacity() {
+        ReverseUintArray::new::<u64>().append(0).append(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "append: item=16 does not fit (len=0, cap=30, size=4)")]
+    fn test_reverse_append_does_not_fit() {
+        ReverseUintArray::new_size(4).append(16);
     }
 }